@@ -121,6 +121,12 @@ fn get_bitfield<T: BitField>(v: &Vec<T>) {
     }
 }
 
+fn toggle_bitfield<T: BitField>(v: &mut Vec<T>) {
+    for i in 0..v.len() * T::BIT_LENGTH {
+        v.as_mut_slice().toggle_bit(i);
+    }
+}
+
 fn set_trivial<T: BitOper>(v: &mut Vec<T>) {
     for i in 0..v.len() * T::BIT_LEN {
         v.set_b(i, true);
@@ -133,6 +139,12 @@ fn get_trivial<T: BitOper>(v: &Vec<T>) {
     }
 }
 
+fn toggle_trivial<T: BitOper>(v: &mut Vec<T>) {
+    for i in 0..v.len() * T::BIT_LEN {
+        v.toggle(i);
+    }
+}
+
 #[bench]
 fn u8_set_bitfield(b: &mut Bencher) {
     let mut v = vec![0u8; LEN];
@@ -231,3 +243,51 @@ fn u64_get_trivial(b: &mut Bencher) {
         get_trivial(&v);
     });
 }
+
+#[bench]
+fn u8_toggle_bitfield(b: &mut Bencher) {
+    let mut v = vec![0u8; LEN];
+    b.iter(|| {
+        toggle_bitfield(&mut v);
+    });
+}
+
+#[bench]
+fn u8_toggle_trivial(b: &mut Bencher) {
+    let mut v = vec![0u8; LEN];
+    b.iter(|| {
+        toggle_trivial(&mut v);
+    });
+}
+
+#[bench]
+fn u32_toggle_bitfield(b: &mut Bencher) {
+    let mut v = vec![0u32; LEN];
+    b.iter(|| {
+        toggle_bitfield(&mut v);
+    });
+}
+
+#[bench]
+fn u32_toggle_trivial(b: &mut Bencher) {
+    let mut v = vec![0u32; LEN];
+    b.iter(|| {
+        toggle_trivial(&mut v);
+    });
+}
+
+#[bench]
+fn u64_toggle_bitfield(b: &mut Bencher) {
+    let mut v = vec![0u64; LEN];
+    b.iter(|| {
+        toggle_bitfield(&mut v);
+    });
+}
+
+#[bench]
+fn u64_toggle_trivial(b: &mut Bencher) {
+    let mut v = vec![0u64; LEN];
+    b.iter(|| {
+        toggle_trivial(&mut v);
+    });
+}
@@ -0,0 +1,193 @@
+//! A fixed-size bit array which is stored inline, without allocating.
+
+use core::array;
+use core::ops::{Range, RangeBounds};
+
+use bounded::checked_range;
+use {const_fn, BitArray, BitArrayMut, BitField, BitSlice, BitSliceMut};
+
+/// Returns the number of elements of the type `T` needed to store `bits` bits, which is the value
+/// of the `WORDS` parameter of a [`BitArr`] of `bits` bits, see [`bit_arr!`].
+///
+/// ```rust
+/// use bit_field::elements_for;
+///
+/// assert_eq!(elements_for::<u8>(10), 2);
+/// assert_eq!(elements_for::<u32>(64), 2);
+/// assert_eq!(elements_for::<u64>(0), 0);
+/// ```
+#[inline]
+pub const fn elements_for<T: BitField>(bits: usize) -> usize {
+    // `usize::div_ceil` requires Rust 1.73
+    bits / T::BIT_LENGTH + (bits % T::BIT_LENGTH != 0) as usize
+}
+
+/// Expands to the type of a [`BitArr`] of `bits` bits with the element type `T` (`usize` by
+/// default), e.g. `bit_arr!(96, u32)`, computing its `WORDS` parameter with [`elements_for`].
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate bit_field;
+///
+/// use bit_field::{BitArr, BitArray};
+///
+/// const CPUS: usize = 96;
+///
+/// type CpuMask = bit_arr!(CPUS, u32);
+/// type Bitmap = bit_arr!(200);
+///
+/// fn main() {
+///     let mask: BitArr<96, 3, u32> = CpuMask::new();
+///     assert_eq!(mask.bit_length(), 96);
+///     assert_eq!(Bitmap::ONES.count_ones_in(..), 200);
+/// }
+/// ```
+#[macro_export]
+macro_rules! bit_arr {
+    ($bits:expr) => {
+        $crate::bit_arr!($bits, usize)
+    };
+    ($bits:expr, $t:ty $(,)?) => {
+        $crate::BitArr<{ $bits }, { $crate::elements_for::<$t>($bits) }, $t>
+    };
+}
+
+/// A bit array of exactly `BITS` bits, which is stored inline in `WORDS` elements of the type
+/// `T`. The type is usually written with the [`bit_arr!`] macro, e.g. `bit_arr!(96, u32)`, which
+/// computes `WORDS` from `BITS` and `T`.
+///
+/// Stable Rust can't compute the number of elements from `BITS` in the type itself, so it is
+/// passed as `WORDS`, which must be [`elements_for::<T>(BITS)`](elements_for). All [`BitArray`]
+/// and [`BitArrayMut`] operations are bounds-checked against `BITS` rather than the bit length of the elements, and
+/// the unused bits of the last element are always kept zeroed.
+///
+/// The constructors are `const fn`s, so bit arrays can be placed in `static`s:
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate bit_field;
+///
+/// use bit_field::{BitArray, BitArrayMut};
+///
+/// type CpuMask = bit_arr!(96, u32);
+///
+/// static ALL_CPUS: CpuMask = CpuMask::ONES;
+///
+/// fn main() {
+///     let mut online = CpuMask::new();
+///     online.set_range(0..4);
+///
+///     assert_eq!(online.count_ones_in(..), 4);
+///     assert_eq!(ALL_CPUS.first_zero(), None);
+///     assert_eq!(ALL_CPUS.as_slice(), &[!0, !0, !0]);
+/// }
+/// ```
+///
+/// A `WORDS` parameter which doesn't match `BITS` is rejected at compile time:
+///
+/// ```rust,compile_fail
+/// use bit_field::BitArr;
+///
+/// let bits = BitArr::<100, 1, u64>::new();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitArr<const BITS: usize, const WORDS: usize, T = usize> {
+    elements: [T; WORDS],
+}
+
+impl<const BITS: usize, const WORDS: usize, T: BitField> BitArr<BITS, WORDS, T> {
+    /// Evaluated by all constructors, so that a mismatched `WORDS` fails to compile.
+    const VALID: () = assert!(
+        WORDS == elements_for::<T>(BITS),
+        "`WORDS` must be `elements_for::<T>(BITS)`"
+    );
+
+    /// Returns the number of bits in the bit array, which is `BITS`.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        BITS
+    }
+
+    /// Returns `true` if the bit array contains no bits.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        BITS == 0
+    }
+
+    /// Returns the elements in which the bits are stored; the unused bits of the last element
+    /// are zero.
+    #[inline]
+    pub const fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the bit array and returns the elements in which the bits are stored; the unused
+    /// bits of the last element are zero.
+    #[inline]
+    pub fn into_inner(self) -> [T; WORDS] {
+        self.elements
+    }
+
+    /// Returns the elements and the range of their bits which belong to the bit array.
+    #[inline]
+    fn storage(&self) -> (&[T], Range<usize>) {
+        (&self.elements, 0..BITS)
+    }
+
+    /// Returns the mutable elements and the range of their bits which belong to the bit array.
+    #[inline]
+    fn storage_mut(&mut self) -> (&mut [T], Range<usize>) {
+        (&mut self.elements, 0..BITS)
+    }
+}
+
+impl<const BITS: usize, const WORDS: usize, T: BitField> Default for BitArr<BITS, WORDS, T> {
+    #[inline]
+    fn default() -> Self {
+        let () = Self::VALID;
+
+        BitArr {
+            elements: array::from_fn(|_| T::from_raw_bits(0)),
+        }
+    }
+}
+
+bounded_bit_array_impl!(
+    [const BITS: usize, const WORDS: usize, T: BitField] BitArr<BITS, WORDS, T>
+);
+bounded_bit_array_mut_impl!(
+    [const BITS: usize, const WORDS: usize, T: BitField] BitArr<BITS, WORDS, T>
+);
+
+/// Implements the `const` constructors of `BitArr` for the given element types, which can't be
+/// written generically because trait methods can't be called in `const` contexts.
+macro_rules! bit_arr_const_impl {
+    ($($t:ident)*) => ($(
+        impl<const BITS: usize, const WORDS: usize> BitArr<BITS, WORDS, $t> {
+            /// A bit array with all bits cleared.
+            pub const ZERO: Self = Self::new();
+
+            /// A bit array with all bits set.
+            pub const ONES: Self = {
+                let () = Self::VALID;
+
+                let mut elements = [!0; WORDS];
+                let rem = BITS % const_fn::$t::BIT_LENGTH;
+                if rem != 0 {
+                    elements[WORDS - 1] = const_fn::$t::mask(0, rem);
+                }
+                BitArr { elements }
+            };
+
+            /// Creates a bit array with all bits cleared.
+            #[inline]
+            pub const fn new() -> Self {
+                let () = Self::VALID;
+
+                BitArr { elements: [0; WORDS] }
+            }
+        }
+    )*)
+}
+
+bit_arr_const_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
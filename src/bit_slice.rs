@@ -0,0 +1,297 @@
+//! Borrowed views of a range of bits of a bit array.
+
+use core::fmt;
+use core::ops::{Range, RangeBounds};
+
+use bounded::checked_range;
+use {to_regular_range, BitArray, BitArrayMut, BitField};
+
+/// A shared view of a range of bits of a bit array, which may start and end anywhere within the
+/// elements.
+///
+/// All indexes are relative to the start of the view and are checked against its length rather
+/// than against the bit length of the elements. Views are created by [`BitArray::bit_slice`] or
+/// [`BitSlice::new`]. A `BitSlice` implements [`BitArray`], so it can be passed wherever a
+/// read-only bit array is expected, and is `Copy`, like a shared slice.
+///
+/// ```rust
+/// use bit_field::BitArray;
+///
+/// let bitmap = [0xf0u8, 0x0f, 0xff];
+/// let view = bitmap.bit_slice(4..20);
+///
+/// assert_eq!(view.len(), 16);
+/// assert_eq!(view.get_bits(0..8), 0xff);
+/// assert_eq!(view.first_zero(), Some(8));
+///
+/// let (low, high) = view.split_at(12);
+/// assert_eq!(low.count_ones_in(..), 8);
+/// assert!(high.iter_bits().all(|bit| bit));
+/// ```
+pub struct BitSlice<'a, T: 'a> {
+    elements: &'a [T],
+    start: usize,
+    end: usize,
+}
+
+impl<'a, T: BitField> BitSlice<'a, T> {
+    /// Creates a view of the bits of `elements` in the range `range`.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitSlice};
+    ///
+    /// let view = BitSlice::new(&[0xabcdu16], 4..12);
+    /// assert_eq!(view.get_bits(..), 0xbc);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of `elements`.
+    #[track_caller]
+    #[inline]
+    pub fn new<U: RangeBounds<usize>>(elements: &'a [T], range: U) -> Self {
+        let bits = to_regular_range(&range, elements.bit_length());
+        assert!(bits.start <= bits.end);
+        assert!(bits.end <= elements.bit_length());
+
+        BitSlice {
+            elements,
+            start: bits.start,
+            end: bits.end,
+        }
+    }
+
+    /// Returns the number of bits in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` if the view contains no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Returns a view of the range `range` of this view.
+    ///
+    /// Unlike [`bit_slice`](BitArray::bit_slice), the returned view borrows the underlying
+    /// elements rather than this view, so it can outlive it.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0x1234_5678u32];
+    /// let view = bitmap.bit_slice(8..24).slice(4..12);
+    ///
+    /// assert_eq!(view.get_bits(..), 0x45);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the view.
+    #[track_caller]
+    #[inline]
+    pub fn slice<U: RangeBounds<usize>>(&self, range: U) -> BitSlice<'a, T> {
+        BitSlice::new(
+            self.elements,
+            checked_range(&range, &(self.start..self.end)),
+        )
+    }
+
+    /// Splits the view into the bits before `mid` and the bits starting at `mid`.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `mid` is greater than the length of the view.
+    #[track_caller]
+    #[inline]
+    pub fn split_at(&self, mid: usize) -> (BitSlice<'a, T>, BitSlice<'a, T>) {
+        (self.slice(..mid), self.slice(mid..))
+    }
+
+    /// Returns the elements and the range of their bits which belong to the view.
+    #[inline]
+    fn storage(&self) -> (&[T], Range<usize>) {
+        (self.elements, self.start..self.end)
+    }
+}
+
+bounded_bit_array_impl!(['a, T: BitField] BitSlice<'a, T>);
+
+impl<'a, T> Clone for BitSlice<'a, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for BitSlice<'a, T> {}
+
+impl<'a, T: BitField> fmt::Debug for BitSlice<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // print the bits from most to least significant, like a binary number
+        f.write_str("BitSlice(0b")?;
+        for bit in self.iter_bits().rev() {
+            f.write_str(if bit { "1" } else { "0" })?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// A mutable view of a range of bits of a bit array, which may start and end anywhere within the
+/// elements.
+///
+/// All indexes are relative to the start of the view and are checked against its length, so the
+/// bits outside of the view are never modified. A `BitSliceMut` implements [`BitArray`] and
+/// [`BitArrayMut`]. Views are created by [`BitArrayMut::bit_slice_mut`] or [`BitSliceMut::new`].
+///
+/// Two mutable views can't share an element of the underlying bit array, so unlike a shared view,
+/// a mutable view can only be split at an element boundary, with
+/// [`split_at_element_mut`](BitSliceMut::split_at_element_mut). To work on two parts which share
+/// an element, take one mutable view after the other, e.g. with
+/// [`slice_mut`](BitSliceMut::slice_mut).
+///
+/// ```rust
+/// use bit_field::{BitArray, BitArrayMut};
+///
+/// let mut bitmap = [0u8; 3];
+///
+/// let mut view = bitmap.bit_slice_mut(4..20);
+/// view.set_bits(0..8, 0xab);
+/// view.set_range(12..);
+/// assert_eq!(view.bit_length(), 16);
+///
+/// assert_eq!(bitmap, [0xb0, 0x0a, 0x0f]);
+/// ```
+pub struct BitSliceMut<'a, T: 'a> {
+    elements: &'a mut [T],
+    bits: Range<usize>,
+}
+
+impl<'a, T: BitField> BitSliceMut<'a, T> {
+    /// Creates a mutable view of the bits of `elements` in the range `range`.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of `elements`.
+    #[track_caller]
+    #[inline]
+    pub fn new<U: RangeBounds<usize>>(elements: &'a mut [T], range: U) -> Self {
+        let bits = to_regular_range(&range, elements.bit_length());
+        assert!(bits.start <= bits.end);
+        assert!(bits.end <= elements.bit_length());
+
+        BitSliceMut { elements, bits }
+    }
+
+    /// Returns the number of bits in the view.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    /// Returns `true` if the view contains no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    /// Returns a shared view of the same bits.
+    #[inline]
+    pub fn as_bit_slice(&self) -> BitSlice<'_, T> {
+        BitSlice::new(&*self.elements, self.bits.clone())
+    }
+
+    /// Returns a mutable view of the range `range` of this view.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut bitmap = [0u16];
+    ///
+    /// bitmap.bit_slice_mut(4..12).slice_mut(2..6).set_range(..);
+    /// assert_eq!(bitmap, [0b11_1100_0000]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the view.
+    #[track_caller]
+    #[inline]
+    pub fn slice_mut<U: RangeBounds<usize>>(&mut self, range: U) -> BitSliceMut<'_, T> {
+        let range = checked_range(&range, &self.bits);
+        BitSliceMut::new(&mut *self.elements, range)
+    }
+
+    /// Splits the view into the bits before `mid` and the bits starting at `mid`, which must be
+    /// an element boundary.
+    ///
+    /// Two mutable views can't share an element, so the split point must fall on an element
+    /// boundary of the underlying bit array, or at the start or end of the view. Shared views can
+    /// be split anywhere with [`BitSlice::split_at`].
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut bitmap = [0u8; 4];
+    ///
+    /// let (mut low, mut high) = bitmap.bit_slice_mut(4..28).split_at_element_mut(12);
+    /// low.set_range(..);
+    /// high.set_bits(0..4, 0b1010);
+    /// assert_eq!(bitmap, [0xf0, 0xff, 0x0a, 0]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `mid` is greater than the length of the view, or if it doesn't
+    /// fall on an element boundary.
+    #[track_caller]
+    #[inline]
+    pub fn split_at_element_mut(self, mid: usize) -> (BitSliceMut<'a, T>, BitSliceMut<'a, T>) {
+        assert!(mid <= self.len());
+
+        let bits = self.bits;
+        let split = bits.start + mid;
+        if mid == 0 {
+            let (low, high) = self.elements.split_at_mut(0);
+            return (BitSliceMut::new(low, 0..0), BitSliceMut::new(high, bits));
+        }
+        if split == bits.end {
+            let len = self.elements.len();
+            let (low, high) = self.elements.split_at_mut(len);
+            return (BitSliceMut::new(low, bits), BitSliceMut::new(high, 0..0));
+        }
+        assert!(
+            split % T::BIT_LENGTH == 0,
+            "split point must be at an element boundary"
+        );
+
+        let (low, high) = self.elements.split_at_mut(split / T::BIT_LENGTH);
+        (
+            BitSliceMut::new(low, bits.start..split),
+            BitSliceMut::new(high, 0..bits.end - split),
+        )
+    }
+
+    /// Returns the elements and the range of their bits which belong to the view.
+    #[inline]
+    fn storage(&self) -> (&[T], Range<usize>) {
+        (&*self.elements, self.bits.clone())
+    }
+
+    /// Returns the mutable elements and the range of their bits which belong to the view.
+    #[inline]
+    fn storage_mut(&mut self) -> (&mut [T], Range<usize>) {
+        (&mut *self.elements, self.bits.clone())
+    }
+}
+
+bounded_bit_array_impl!(['a, T: BitField] BitSliceMut<'a, T>);
+bounded_bit_array_mut_impl!(['a, T: BitField] BitSliceMut<'a, T>);
+
+impl<'a, T: BitField> fmt::Debug for BitSliceMut<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_bit_slice(), f)
+    }
+}
@@ -0,0 +1,298 @@
+//! An owned, growable bit vector, which is only available with the `alloc` feature.
+
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+use core::ops::{Range, RangeBounds};
+
+use bounded::checked_range;
+use {element_ranges, elements_for, BitArray, BitArrayMut, BitField, BitSlice, BitSliceMut};
+
+/// A growable array of bits with an exact bit length, which is stored in elements of the type
+/// `T`.
+///
+/// Unlike a plain `[T]`, whose [`bit_length`](BitArray::bit_length) is always a multiple of
+/// `T::BIT_LENGTH`, a `BitVec` only contains the bits which were pushed to it; all [`BitArray`]
+/// and [`BitArrayMut`] operations are bounds-checked against [`len`](BitVec::len). The unused
+/// bits of the last element are always kept zeroed, so two bit vectors with the same bits compare
+/// equal.
+///
+/// ```rust
+/// use bit_field::{BitArray, BitVec};
+///
+/// let mut bits: BitVec<u8> = BitVec::new();
+/// bits.resize(10, true);
+/// bits.push(false);
+///
+/// assert_eq!(bits.len(), 11);
+/// assert_eq!(bits.count_ones_in(..), 10);
+/// assert_eq!(bits.first_zero(), Some(10));
+/// assert_eq!(bits.as_slice(), &[0xff, 0b011]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BitVec<T> {
+    elements: Vec<T>,
+    len: usize,
+}
+
+impl<T: BitField> BitVec<T> {
+    /// Creates an empty bit vector without allocating.
+    ///
+    /// ```rust
+    /// use bit_field::BitVec;
+    ///
+    /// let bits: BitVec<u32> = BitVec::new();
+    /// assert!(bits.is_empty());
+    /// ```
+    #[inline]
+    pub fn new() -> Self {
+        BitVec {
+            elements: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Creates an empty bit vector which can hold at least `capacity` bits without reallocating.
+    ///
+    /// ```rust
+    /// use bit_field::BitVec;
+    ///
+    /// let bits: BitVec<u32> = BitVec::with_capacity(100);
+    /// assert!(bits.capacity() >= 100);
+    /// ```
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        BitVec {
+            elements: Vec::with_capacity(elements_for::<T>(capacity)),
+            len: 0,
+        }
+    }
+
+    /// Creates a bit vector of `len` bits which are all set to `value`.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitVec};
+    ///
+    /// let bits: BitVec<u64> = BitVec::repeat(true, 100);
+    ///
+    /// assert_eq!(bits.len(), 100);
+    /// assert_eq!(bits.count_ones_in(..), 100);
+    /// ```
+    #[inline]
+    pub fn repeat(value: bool, len: usize) -> Self {
+        let mut bits = BitVec::new();
+        bits.resize(len, value);
+        bits
+    }
+
+    /// Returns the number of bits in the bit vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the bit vector contains no bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bits the bit vector can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.elements.capacity() * T::BIT_LENGTH
+    }
+
+    /// Returns the elements in which the bits are stored; the unused bits of the last element
+    /// are zero.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.elements
+    }
+
+    /// Consumes the bit vector and returns the elements in which the bits are stored; the unused
+    /// bits of the last element are zero.
+    #[inline]
+    pub fn into_vec(self) -> Vec<T> {
+        self.elements
+    }
+
+    /// Appends a bit to the end of the bit vector.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitVec};
+    ///
+    /// let mut bits: BitVec<u8> = BitVec::new();
+    /// bits.push(true);
+    /// bits.push(false);
+    ///
+    /// assert_eq!(bits.len(), 2);
+    /// assert_eq!(bits.get_bit(0), true);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: bool) {
+        if self.len == self.elements.len() * T::BIT_LENGTH {
+            self.elements.push(T::from_raw_bits(0));
+        }
+        self.len += 1;
+        self.set_bit(self.len - 1, value);
+    }
+
+    /// Removes the last bit from the bit vector and returns it, or `None` if it is empty.
+    ///
+    /// ```rust
+    /// use bit_field::BitVec;
+    ///
+    /// let mut bits: BitVec<u8> = BitVec::repeat(true, 1);
+    ///
+    /// assert_eq!(bits.pop(), Some(true));
+    /// assert_eq!(bits.pop(), None);
+    /// ```
+    #[inline]
+    pub fn pop(&mut self) -> Option<bool> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let value = self.get_bit(self.len - 1);
+        self.truncate(self.len - 1);
+        Some(value)
+    }
+
+    /// Shortens the bit vector to `len` bits; this has no effect if the bit vector is already
+    /// shorter.
+    ///
+    /// ```rust
+    /// use bit_field::BitVec;
+    ///
+    /// let mut bits: BitVec<u8> = BitVec::repeat(true, 12);
+    /// bits.truncate(3);
+    ///
+    /// assert_eq!(bits.as_slice(), &[0b111]);
+    /// ```
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.len {
+            return;
+        }
+
+        self.elements.truncate(elements_for::<T>(len));
+        self.elements.clear_range(len..);
+        self.len = len;
+    }
+
+    /// Removes all bits from the bit vector.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.elements.clear();
+        self.len = 0;
+    }
+
+    /// Resizes the bit vector to `len` bits; if it grows, the new bits are set to `value`.
+    ///
+    /// ```rust
+    /// use bit_field::BitVec;
+    ///
+    /// let mut bits: BitVec<u8> = BitVec::new();
+    ///
+    /// bits.resize(4, true);
+    /// bits.resize(10, false);
+    /// assert_eq!(bits.as_slice(), &[0b1111, 0]);
+    ///
+    /// bits.resize(2, true);
+    /// assert_eq!(bits.as_slice(), &[0b11]);
+    /// ```
+    #[inline]
+    pub fn resize(&mut self, len: usize, value: bool) {
+        if len <= self.len {
+            self.truncate(len);
+            return;
+        }
+
+        let start = self.len;
+        self.elements
+            .resize_with(elements_for::<T>(len), || T::from_raw_bits(0));
+        self.len = len;
+        if value {
+            self.set_range(start..len);
+        }
+    }
+
+    /// Appends the range of bits specified by `range` of the bit array `bits` to the end of the
+    /// bit vector.
+    ///
+    /// ```rust
+    /// use bit_field::BitVec;
+    ///
+    /// let mut bits: BitVec<u8> = BitVec::new();
+    /// bits.extend_from_bits(&[0xcd, 0xab], 4..16);
+    ///
+    /// assert_eq!(bits.len(), 12);
+    /// assert_eq!(bits.as_slice(), &[0xbc, 0xa]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of `bits`.
+    #[track_caller]
+    #[inline]
+    pub fn extend_from_bits<U: RangeBounds<usize>>(&mut self, bits: &[T], range: U) {
+        self.extend_from_bitslice(bits.bit_slice(range));
+    }
+
+    /// Appends the bits of the view `bits` to the end of the bit vector.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitVec};
+    ///
+    /// let mut bits: BitVec<u8> = BitVec::new();
+    /// bits.extend_from_bitslice([0xcd, 0xab].bit_slice(4..16));
+    ///
+    /// let copy = bits.clone();
+    /// bits.extend_from_bitslice(copy.bit_slice(8..));
+    ///
+    /// assert_eq!(bits.len(), 16);
+    /// assert_eq!(bits.as_slice(), &[0xbc, 0xaa]);
+    /// ```
+    #[inline]
+    pub fn extend_from_bitslice(&mut self, bits: BitSlice<'_, T>) {
+        let start = self.len;
+        self.resize(start + bits.len(), false);
+        for chunk in element_ranges(0..bits.len(), bits.len(), T::BIT_LENGTH) {
+            let value = bits.get_bits(chunk.clone());
+            self.set_bits(start + chunk.start..start + chunk.end, value);
+        }
+    }
+
+    /// Returns the elements and the range of their bits which belong to the bit vector.
+    #[inline]
+    fn storage(&self) -> (&[T], Range<usize>) {
+        (&self.elements, 0..self.len)
+    }
+
+    /// Returns the mutable elements and the range of their bits which belong to the bit vector.
+    #[inline]
+    fn storage_mut(&mut self) -> (&mut [T], Range<usize>) {
+        (&mut self.elements, 0..self.len)
+    }
+}
+
+bounded_bit_array_impl!([T: BitField] BitVec<T>);
+bounded_bit_array_mut_impl!([T: BitField] BitVec<T>);
+
+impl<T: BitField> Extend<bool> for BitVec<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = bool>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T: BitField> FromIterator<bool> for BitVec<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new();
+        bits.extend(iter);
+        bits
+    }
+}
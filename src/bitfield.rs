@@ -0,0 +1,271 @@
+//! The `bitfield!` macro, which defines newtypes over integers with typed accessors for their
+//! fields.
+
+/// Defines a newtype over an integer type with typed accessors for bit fields.
+///
+/// Each field is declared as `name: start..end`, optionally followed by `=> Type`, which
+/// generates the getter `name`. The bounds may be any constant expressions, e.g.
+/// `OFFSET..OFFSET + 4`. Fields are read-only unless the names of a setter and a `const fn`
+/// builder are given after the getter, as in `name, set_name, with_name: start..end`;
+/// `macro_rules!` can't create identifiers, so these names can't be derived from `name`. The
+/// accessors take and return the field type, which is the storage type by default, `bool` for
+/// single-bit fields or another unsigned integer type which can hold the field. Attributes, such
+/// as documentation comments, are applied to the getter.
+///
+/// The generated type has `const fn`s `new`, which creates a value with all bits cleared,
+/// `from_bits` and `bits`, and implements `Debug` by listing the values of its fields. Fields
+/// which are empty, out of bounds of the storage type, overlap each other or don't fit into their
+/// type are rejected at compile time.
+///
+/// ```rust
+/// #[macro_use]
+/// extern crate bit_field;
+///
+/// const PROTECTION_KEY: usize = 59;
+///
+/// bitfield! {
+///     /// An x86_64 page table entry.
+///     #[derive(PartialEq, Eq)]
+///     pub struct PageTableEntry(u64) {
+///         /// Whether the entry is present.
+///         pub present, set_present, with_present: 0..1 => bool,
+///         pub writable, set_writable, with_writable: 1..2 => bool,
+///         /// Set by the CPU when the entry is used for a translation.
+///         pub accessed: 5..6 => bool,
+///         pub frame, set_frame, with_frame: 12..52,
+///         pub protection_key, set_protection_key, with_protection_key:
+///             PROTECTION_KEY..PROTECTION_KEY + 4 => u8,
+///     }
+/// }
+///
+/// const KERNEL_CODE: PageTableEntry = PageTableEntry::new().with_present(true).with_frame(0x1a2);
+///
+/// fn main() {
+///     let mut entry = KERNEL_CODE;
+///     entry.set_protection_key(3);
+///
+///     assert_eq!(entry.bits(), 0x1800_0000_001a_2001);
+///     assert_eq!(entry.frame(), 0x1a2);
+///     assert!(!entry.writable());
+///     assert!(PageTableEntry::from_bits(0x20).accessed());
+///     assert_eq!(
+///         format!("{:?}", entry),
+///         "PageTableEntry { present: true, writable: false, accessed: false, frame: 418, \
+///          protection_key: 3 }"
+///     );
+/// }
+/// ```
+///
+/// Overlapping fields fail to compile:
+///
+/// ```rust,compile_fail
+/// #[macro_use]
+/// extern crate bit_field;
+///
+/// bitfield! {
+///     struct Flags(u8) {
+///         low: 0..4,
+///         high: 3..8,
+///     }
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// Field types must be unsigned, so a signed field type fails to compile as well:
+///
+/// ```rust,compile_fail
+/// #[macro_use]
+/// extern crate bit_field;
+///
+/// bitfield! {
+///     struct Offset(u16) {
+///         delta: 0..8 => i8,
+///     }
+/// }
+///
+/// fn main() {}
+/// ```
+///
+/// ## Panics
+///
+/// The setters and builders panic if the value does not fit into the field, which is a compile
+/// time error in `const` contexts.
+#[macro_export]
+macro_rules! bitfield {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident($storage:ident) {
+            $(
+                $(#[$field_attr:meta])*
+                $field_vis:vis $get:ident $(, $set:ident, $with:ident)?: $range:expr
+                    $(=> $ty:ident)?
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Default, Hash)]
+        #[repr(transparent)]
+        $vis struct $name($storage);
+
+        impl $name {
+            /// Creates a value with all bits cleared.
+            #[inline]
+            pub const fn new() -> Self {
+                $name(0)
+            }
+
+            /// Creates a value from its raw bits.
+            #[inline]
+            pub const fn from_bits(bits: $storage) -> Self {
+                $name(bits)
+            }
+
+            /// Returns the raw bits of the value.
+            #[inline]
+            pub const fn bits(&self) -> $storage {
+                self.0
+            }
+
+            $(
+                $crate::bitfield!(
+                    @accessors $name($storage),
+                    [$(#[$field_attr])*] $field_vis $get [$($set, $with)?]: ($range) [$($ty)?]
+                );
+            )*
+        }
+
+        impl $crate::__private::fmt::Debug for $name {
+            fn fmt(
+                &self,
+                f: &mut $crate::__private::fmt::Formatter,
+            ) -> $crate::__private::fmt::Result {
+                f.debug_struct($crate::__private::stringify!($name))
+                    $(.field($crate::__private::stringify!($get), &self.$get()))*
+                    .finish()
+            }
+        }
+
+        const _: () = {
+            $crate::__private::check_fields(
+                &[$((($range).start, ($range).end)),*],
+                $crate::const_fn::$storage::BIT_LENGTH,
+            );
+            $($crate::bitfield!(@check $storage, $range $(, $ty)?);)*
+        };
+    };
+
+    (
+        @accessors $name:ident($storage:ident),
+        [$(#[$field_attr:meta])*] $field_vis:vis $get:ident []: ($range:expr) [$($ty:ident)?]
+    ) => {
+        $(#[$field_attr])*
+        #[inline]
+        $field_vis const fn $get(&self) -> $crate::bitfield!(@type $storage $($ty)?) {
+            $crate::bitfield!(@get $storage, self.0, $range $(, $ty)?)
+        }
+    };
+    (
+        @accessors $name:ident($storage:ident),
+        [$(#[$field_attr:meta])*] $field_vis:vis $get:ident [$set:ident, $with:ident]:
+            ($range:expr) [$($ty:ident)?]
+    ) => {
+        $crate::bitfield!(
+            @accessors $name($storage),
+            [$(#[$field_attr])*] $field_vis $get []: ($range) [$($ty)?]
+        );
+
+        #[doc = $crate::__private::concat!(
+            "Sets the [`", $crate::__private::stringify!($get), "`](Self::",
+            $crate::__private::stringify!($get), ") field."
+        )]
+        #[track_caller]
+        #[inline]
+        $field_vis fn $set(&mut self, value: $crate::bitfield!(@type $storage $($ty)?)) {
+            *self = self.$with(value);
+        }
+
+        #[doc = $crate::__private::concat!(
+            "Returns a copy with the [`", $crate::__private::stringify!($get), "`](Self::",
+            $crate::__private::stringify!($get), ") field set to `value`."
+        )]
+        #[must_use]
+        #[track_caller]
+        #[inline]
+        $field_vis const fn $with(self, value: $crate::bitfield!(@type $storage $($ty)?)) -> Self {
+            $name($crate::bitfield!(@set $storage, self.0, $range, value $(, $ty)?))
+        }
+    };
+
+    (@type $storage:ident) => { $storage };
+    (@type $storage:ident $ty:ident) => { $ty };
+
+    (@get $storage:ident, $bits:expr, $range:expr) => {
+        $crate::const_fn::$storage::get_bits($bits, ($range).start, ($range).end)
+    };
+    (@get $storage:ident, $bits:expr, $range:expr, bool) => {
+        $crate::const_fn::$storage::get_bit($bits, ($range).start)
+    };
+    (@get $storage:ident, $bits:expr, $range:expr, $ty:ident) => {
+        $crate::const_fn::$storage::get_bits($bits, ($range).start, ($range).end) as $ty
+    };
+
+    (@set $storage:ident, $bits:expr, $range:expr, $value:expr) => {
+        $crate::const_fn::$storage::with_bits($bits, ($range).start, ($range).end, $value)
+    };
+    (@set $storage:ident, $bits:expr, $range:expr, $value:expr, bool) => {
+        $crate::const_fn::$storage::with_bit($bits, ($range).start, $value)
+    };
+    (@set $storage:ident, $bits:expr, $range:expr, $value:expr, $ty:ident) => {
+        $crate::const_fn::$storage::with_bits(
+            $bits,
+            ($range).start,
+            ($range).end,
+            $value as $storage,
+        )
+    };
+
+    (@check $storage:ident, $range:expr) => {};
+    (@check $storage:ident, $range:expr, bool) => {
+        $crate::__private::assert!(
+            ($range).end - ($range).start == 1,
+            "`bool` fields must be one bit long"
+        );
+    };
+    (@check $storage:ident, $range:expr, $ty:ident) => {
+        $crate::__private::assert!(<$ty>::MIN == 0, "bit field types must be unsigned");
+        $crate::__private::assert!(
+            ($range).end - ($range).start <= $crate::const_fn::$ty::BIT_LENGTH,
+            "the field does not fit into its type"
+        );
+        $crate::__private::assert!(
+            $crate::const_fn::$ty::BIT_LENGTH <= $crate::const_fn::$storage::BIT_LENGTH,
+            "the field type is wider than the storage type"
+        );
+    };
+}
+
+/// Checks that the fields `fields` of a `bitfield!` type are non-empty, in bounds of the storage
+/// type and don't overlap.
+pub const fn check_fields(fields: &[(usize, usize)], bit_length: usize) {
+    let mut index = 0;
+    while index < fields.len() {
+        let (start, end) = fields[index];
+        assert!(start < end, "the field range is empty");
+        assert!(
+            end <= bit_length,
+            "the field range is out of bounds of the storage type"
+        );
+
+        let mut other = 0;
+        while other < index {
+            let (other_start, other_end) = fields[other];
+            assert!(
+                end <= other_start || other_end <= start,
+                "the field ranges overlap"
+            );
+            other += 1;
+        }
+        index += 1;
+    }
+}
@@ -0,0 +1,39 @@
+//! Implementations of [`BitField::extract_mask`](::BitField::extract_mask) and
+//! [`BitField::deposit_mask`](::BitField::deposit_mask) on top of the BMI2 `pext` and `pdep`
+//! instructions, which are only compiled with the `bmi2` feature on `x86_64` targets which enable
+//! the `bmi2` target feature.
+//!
+//! The values are passed zero-extended to `u128` and processed as two 64 bit halves, so the same
+//! functions work for all widths.
+
+use core::arch::x86_64::{_pdep_u64, _pext_u64};
+
+/// Gathers the bits of `value` which are set in `mask` into the lower bits of the result.
+#[inline]
+pub(crate) fn extract_mask(value: u128, mask: u128) -> u128 {
+    let (low_mask, high_mask) = (mask as u64, (mask >> 64) as u64);
+
+    // SAFETY: this module is only compiled if the target enables the `bmi2` target feature
+    let (low, high) = unsafe {
+        (
+            _pext_u64(value as u64, low_mask),
+            _pext_u64((value >> 64) as u64, high_mask),
+        )
+    };
+    low as u128 | (high as u128) << low_mask.count_ones()
+}
+
+/// Returns `value` with the lower bits of `bits` scattered to the bits which are set in `mask`.
+#[inline]
+pub(crate) fn deposit_mask(value: u128, mask: u128, bits: u128) -> u128 {
+    let (low_mask, high_mask) = (mask as u64, (mask >> 64) as u64);
+
+    // SAFETY: this module is only compiled if the target enables the `bmi2` target feature
+    let (low, high) = unsafe {
+        (
+            _pdep_u64(bits as u64, low_mask),
+            _pdep_u64((bits >> low_mask.count_ones()) as u64, high_mask),
+        )
+    };
+    value & !mask | low as u128 | (high as u128) << 64
+}
@@ -0,0 +1,96 @@
+//! The `BitArray` implementation shared by the bit array types which, unlike `[T]`, cover an
+//! arbitrary range of bits of their elements.
+
+use core::ops::{Range, RangeBounds};
+
+use to_regular_range;
+
+/// Implements `BitArray<T>` for a type with a `storage` method returning its elements and the
+/// range of their bits that it covers.
+///
+/// All indexes and ranges are relative to the start of the covered range and are checked against
+/// its length before the access is delegated to the `BitArray` implementation of `[T]`. Only the
+/// required methods are implemented; all others use the default implementations of `BitArray`.
+/// The implementation refers to the items it needs by name, so the invoking module has to import
+/// them.
+macro_rules! bounded_bit_array_impl {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> BitArray<T> for $ty {
+            #[inline]
+            fn bit_length(&self) -> usize {
+                self.storage().1.len()
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bit(&self, bit: usize) -> bool {
+                let (elements, bits) = self.storage();
+                assert!(bit < bits.len());
+
+                elements.get_bit(bits.start + bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T {
+                let (elements, bits) = self.storage();
+
+                elements.get_bits(checked_range(&range, &bits))
+            }
+
+            #[track_caller]
+            #[inline]
+            fn bit_slice<U: RangeBounds<usize>>(&self, range: U) -> BitSlice<'_, T> {
+                let (elements, bits) = self.storage();
+
+                BitSlice::new(elements, checked_range(&range, &bits))
+            }
+        }
+    };
+}
+
+/// Implements `BitArrayMut<T>` for a type which implements `BitArray<T>` with
+/// `bounded_bit_array_impl!` and has a `storage_mut` method returning its mutable elements and
+/// the range of their bits that it covers.
+///
+/// The bits outside of the covered range are never modified.
+macro_rules! bounded_bit_array_mut_impl {
+    ([$($generics:tt)*] $ty:ty) => {
+        impl<$($generics)*> BitArrayMut<T> for $ty {
+            #[track_caller]
+            #[inline]
+            fn set_bit(&mut self, bit: usize, value: bool) {
+                let (elements, bits) = self.storage_mut();
+                assert!(bit < bits.len());
+
+                elements.set_bit(bits.start + bit, value);
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: T) {
+                let (elements, bits) = self.storage_mut();
+
+                elements.set_bits(checked_range(&range, &bits), value);
+            }
+
+            #[track_caller]
+            #[inline]
+            fn bit_slice_mut<U: RangeBounds<usize>>(&mut self, range: U) -> BitSliceMut<'_, T> {
+                let (elements, bits) = self.storage_mut();
+
+                BitSliceMut::new(elements, checked_range(&range, &bits))
+            }
+        }
+    };
+}
+
+/// Converts `range`, which is relative to the start of `bits`, into a regular range of the
+/// underlying elements and checks that it ends within `bits`.
+#[track_caller]
+#[inline]
+pub(crate) fn checked_range<U: RangeBounds<usize>>(range: &U, bits: &Range<usize>) -> Range<usize> {
+    let range = to_regular_range(range, bits.len());
+    assert!(range.end <= bits.len());
+    bits.start + range.start..bits.start + range.end
+}
@@ -0,0 +1,142 @@
+//! Typed descriptors of fixed bit ranges, whose bounds are checked at compile time.
+
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops::Range;
+
+use BitField;
+
+/// A descriptor of the bits `START..END` of a value of the type `T`.
+///
+/// The range is part of the type, so it is checked against `T::BIT_LENGTH` when the descriptor
+/// is created in a `const` context, and [`get`](Field::get) and [`set`](Field::set) access the
+/// bits with a mask and shift without any runtime range checks; `set` only checks that the value
+/// fits into the field. Descriptors are usually declared as constants:
+///
+/// ```rust
+/// use bit_field::Field;
+///
+/// const PRESENT: Field<u64, 0, 1> = Field::new();
+/// const FRAME: Field<u64, 12, 52> = Field::new();
+///
+/// let mut entry = 0u64;
+/// PRESENT.set(&mut entry, 1);
+/// FRAME.set(&mut entry, 0xa_bcde);
+///
+/// assert_eq!(entry, 0xab_cde0_01);
+/// assert_eq!(FRAME.get(&entry), 0xa_bcde);
+/// ```
+///
+/// A range which is empty or out of bounds of `T` is rejected at compile time:
+///
+/// ```rust,compile_fail
+/// use bit_field::Field;
+///
+/// const FLAGS: Field<u32, 24, 40> = Field::new();
+/// ```
+pub struct Field<T, const START: usize, const END: usize> {
+    value: PhantomData<fn(T) -> T>,
+}
+
+impl<T: BitField, const START: usize, const END: usize> Field<T, START, END> {
+    /// Evaluated by the constructor, so that an invalid range fails to compile.
+    const VALID: () = assert!(
+        START < END && END <= T::BIT_LENGTH,
+        "the field range must be non-empty and in bounds of `T`"
+    );
+
+    /// The mask of the lower `END - START` bits.
+    const MASK: u128 = !0 >> (128 - (END - START));
+
+    /// Creates the descriptor.
+    #[inline]
+    pub const fn new() -> Self {
+        let () = Self::VALID;
+
+        Field { value: PhantomData }
+    }
+
+    /// Returns the range of bits which the field covers.
+    #[inline]
+    pub const fn range(&self) -> Range<usize> {
+        START..END
+    }
+
+    /// Returns the number of bits of the field.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        END - START
+    }
+
+    /// Returns `false`, as fields always cover at least one bit.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Obtains the bits of the field from `value`, see [`BitField::get_bits`].
+    #[inline]
+    pub fn get(&self, value: &T) -> T {
+        T::from_raw_bits(value.raw_bits() >> START & Self::MASK)
+    }
+
+    /// Sets the bits of the field in `value` to the lower bits of `bits`, see
+    /// [`BitField::set_bits`].
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `bits` does not fit into the field.
+    #[track_caller]
+    #[inline]
+    pub fn set(&self, value: &mut T, bits: T) {
+        // checks that `bits` fits into the field and strips the sign extension of negative values
+        let bits = T::from_raw_bits(0)
+            .with_bits(0..self.len(), bits)
+            .raw_bits();
+
+        *value = T::from_raw_bits(value.raw_bits() & !(Self::MASK << START) | bits << START);
+    }
+
+    /// Returns a copy of `value` with the bits of the field set to the lower bits of `bits`, see
+    /// [`BitField::with_bits`].
+    ///
+    /// ```rust
+    /// use bit_field::Field;
+    ///
+    /// const MODE: Field<u8, 4, 6> = Field::new();
+    ///
+    /// assert_eq!(MODE.with(0b1000_0001, 0b11), 0b1011_0001);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `bits` does not fit into the field.
+    #[track_caller]
+    #[inline]
+    pub fn with(&self, mut value: T, bits: T) -> T {
+        self.set(&mut value, bits);
+        value
+    }
+}
+
+impl<T: BitField, const START: usize, const END: usize> Default for Field<T, START, END> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const START: usize, const END: usize> Clone for Field<T, START, END> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const START: usize, const END: usize> Copy for Field<T, START, END> {}
+
+impl<T, const START: usize, const END: usize> fmt::Debug for Field<T, START, END> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Field({}..{})", START, END)
+    }
+}
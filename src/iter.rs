@@ -0,0 +1,550 @@
+//! Iterators over the bits of [`BitField`] values and [`BitArray`]s.
+
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::ops::Range;
+use core::slice;
+
+use BitArray;
+use BitField;
+
+/// An iterator over the indexes of the `1`s of a bit field, from least to most significant.
+///
+/// This struct is created by [`BitField::iter_ones`].
+#[derive(Debug, Clone)]
+pub struct Ones<T> {
+    value: T,
+    range: Range<usize>,
+    len: usize,
+}
+
+impl<T: BitField> Ones<T> {
+    #[inline]
+    pub(crate) fn new(value: T) -> Self {
+        Ones {
+            len: value.count_ones_in(..),
+            value,
+            range: 0..T::BIT_LENGTH,
+        }
+    }
+}
+
+impl<T: BitField> Iterator for Ones<T> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bit = slice::from_ref(&self.value).first_one_in(self.range.clone())?;
+        self.range.start = bit + 1;
+        self.len -= 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: BitField> DoubleEndedIterator for Ones<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        let bit = slice::from_ref(&self.value).last_one_in(self.range.clone())?;
+        self.range.end = bit;
+        self.len -= 1;
+        Some(bit)
+    }
+}
+
+impl<T: BitField> ExactSizeIterator for Ones<T> {}
+
+impl<T: BitField> FusedIterator for Ones<T> {}
+
+/// An iterator over the indexes of the `0`s of a bit field, from least to most significant.
+///
+/// This struct is created by [`BitField::iter_zeros`].
+#[derive(Debug, Clone)]
+pub struct Zeros<T> {
+    value: T,
+    range: Range<usize>,
+    len: usize,
+}
+
+impl<T: BitField> Zeros<T> {
+    #[inline]
+    pub(crate) fn new(value: T) -> Self {
+        Zeros {
+            len: value.count_zeros_in(..),
+            value,
+            range: 0..T::BIT_LENGTH,
+        }
+    }
+}
+
+impl<T: BitField> Iterator for Zeros<T> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bit = slice::from_ref(&self.value).first_zero_in(self.range.clone())?;
+        self.range.start = bit + 1;
+        self.len -= 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: BitField> DoubleEndedIterator for Zeros<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        let bit = slice::from_ref(&self.value).last_zero_in(self.range.clone())?;
+        self.range.end = bit;
+        self.len -= 1;
+        Some(bit)
+    }
+}
+
+impl<T: BitField> ExactSizeIterator for Zeros<T> {}
+
+impl<T: BitField> FusedIterator for Zeros<T> {}
+
+/// An iterator over all bits of a bit field as `bool`s, from least to most significant.
+///
+/// This struct is created by [`BitField::iter_bits`].
+#[derive(Debug, Clone)]
+pub struct Bits<T> {
+    value: T,
+    range: Range<usize>,
+}
+
+impl<T: BitField> Bits<T> {
+    #[inline]
+    pub(crate) fn new(value: T) -> Self {
+        Bits {
+            value,
+            range: 0..T::BIT_LENGTH,
+        }
+    }
+}
+
+impl<T: BitField> Iterator for Bits<T> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        let bit = self.range.next()?;
+        Some(self.value.get_bit(bit))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<T: BitField> DoubleEndedIterator for Bits<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<bool> {
+        let bit = self.range.next_back()?;
+        Some(self.value.get_bit(bit))
+    }
+}
+
+impl<T: BitField> ExactSizeIterator for Bits<T> {}
+
+impl<T: BitField> FusedIterator for Bits<T> {}
+
+/// An iterator over the indexes of the `1`s of a bit array, from least to most significant.
+///
+/// Elements without any `1`s are skipped in a single step. This struct is created by [`BitArray::iter_ones`].
+#[derive(Debug)]
+pub struct ArrayOnes<'a, T: 'a, A: 'a + ?Sized = [T]> {
+    array: &'a A,
+    range: Range<usize>,
+    element: PhantomData<T>,
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> ArrayOnes<'a, T, A> {
+    #[inline]
+    pub(crate) fn new(array: &'a A) -> Self {
+        ArrayOnes {
+            array,
+            range: 0..array.bit_length(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: ?Sized> Clone for ArrayOnes<'a, T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArrayOnes {
+            array: self.array,
+            range: self.range.clone(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> Iterator for ArrayOnes<'a, T, A> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bit = self.array.first_one_in(self.range.clone())?;
+        self.range.start = bit + 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.range.len()))
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> DoubleEndedIterator for ArrayOnes<'a, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        let bit = self.array.last_one_in(self.range.clone())?;
+        self.range.end = bit;
+        Some(bit)
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> FusedIterator for ArrayOnes<'a, T, A> {}
+
+/// An iterator over the indexes of the `0`s of a bit array, from least to most significant.
+///
+/// Elements without any `0`s are skipped in a single step. This struct is created by [`BitArray::iter_zeros`].
+#[derive(Debug)]
+pub struct ArrayZeros<'a, T: 'a, A: 'a + ?Sized = [T]> {
+    array: &'a A,
+    range: Range<usize>,
+    element: PhantomData<T>,
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> ArrayZeros<'a, T, A> {
+    #[inline]
+    pub(crate) fn new(array: &'a A) -> Self {
+        ArrayZeros {
+            array,
+            range: 0..array.bit_length(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: ?Sized> Clone for ArrayZeros<'a, T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArrayZeros {
+            array: self.array,
+            range: self.range.clone(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> Iterator for ArrayZeros<'a, T, A> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        let bit = self.array.first_zero_in(self.range.clone())?;
+        self.range.start = bit + 1;
+        Some(bit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.range.len()))
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> DoubleEndedIterator for ArrayZeros<'a, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<usize> {
+        let bit = self.array.last_zero_in(self.range.clone())?;
+        self.range.end = bit;
+        Some(bit)
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> FusedIterator for ArrayZeros<'a, T, A> {}
+
+/// An iterator over all bits of a bit array as `bool`s, from least to most significant.
+///
+/// This struct is created by [`BitArray::iter_bits`].
+#[derive(Debug)]
+pub struct ArrayBits<'a, T: 'a, A: 'a + ?Sized = [T]> {
+    array: &'a A,
+    range: Range<usize>,
+    element: PhantomData<T>,
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> ArrayBits<'a, T, A> {
+    #[inline]
+    pub(crate) fn new(array: &'a A) -> Self {
+        ArrayBits {
+            array,
+            range: 0..array.bit_length(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: ?Sized> Clone for ArrayBits<'a, T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArrayBits {
+            array: self.array,
+            range: self.range.clone(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> Iterator for ArrayBits<'a, T, A> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        let bit = self.range.next()?;
+        Some(self.array.get_bit(bit))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> DoubleEndedIterator for ArrayBits<'a, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<bool> {
+        let bit = self.range.next_back()?;
+        Some(self.array.get_bit(bit))
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> ExactSizeIterator for ArrayBits<'a, T, A> {}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> FusedIterator for ArrayBits<'a, T, A> {}
+
+/// An iterator over the maximal runs of `1`s of a bit field, from least to most significant.
+///
+/// This struct is created by [`BitField::iter_one_runs`].
+#[derive(Debug, Clone)]
+pub struct OneRuns<T> {
+    value: T,
+    range: Range<usize>,
+}
+
+impl<T: BitField> OneRuns<T> {
+    #[inline]
+    pub(crate) fn new(value: T) -> Self {
+        OneRuns {
+            value,
+            range: 0..T::BIT_LENGTH,
+        }
+    }
+}
+
+impl<T: BitField> Iterator for OneRuns<T> {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Range<usize>> {
+        next_run(slice::from_ref(&self.value), &mut self.range, true)
+    }
+}
+
+impl<T: BitField> DoubleEndedIterator for OneRuns<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Range<usize>> {
+        next_run_back(slice::from_ref(&self.value), &mut self.range, true)
+    }
+}
+
+impl<T: BitField> FusedIterator for OneRuns<T> {}
+
+/// An iterator over the maximal runs of `0`s of a bit field, from least to most significant.
+///
+/// This struct is created by [`BitField::iter_zero_runs`].
+#[derive(Debug, Clone)]
+pub struct ZeroRuns<T> {
+    value: T,
+    range: Range<usize>,
+}
+
+impl<T: BitField> ZeroRuns<T> {
+    #[inline]
+    pub(crate) fn new(value: T) -> Self {
+        ZeroRuns {
+            value,
+            range: 0..T::BIT_LENGTH,
+        }
+    }
+}
+
+impl<T: BitField> Iterator for ZeroRuns<T> {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Range<usize>> {
+        next_run(slice::from_ref(&self.value), &mut self.range, false)
+    }
+}
+
+impl<T: BitField> DoubleEndedIterator for ZeroRuns<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Range<usize>> {
+        next_run_back(slice::from_ref(&self.value), &mut self.range, false)
+    }
+}
+
+impl<T: BitField> FusedIterator for ZeroRuns<T> {}
+
+/// An iterator over the maximal runs of `1`s of a bit array, from least to most significant.
+///
+/// Elements which are completely inside or outside of a run are skipped in a single step. This
+/// struct is created by [`BitArray::iter_one_runs`].
+#[derive(Debug)]
+pub struct ArrayOneRuns<'a, T: 'a, A: 'a + ?Sized = [T]> {
+    array: &'a A,
+    range: Range<usize>,
+    element: PhantomData<T>,
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> ArrayOneRuns<'a, T, A> {
+    #[inline]
+    pub(crate) fn new(array: &'a A) -> Self {
+        ArrayOneRuns {
+            array,
+            range: 0..array.bit_length(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: ?Sized> Clone for ArrayOneRuns<'a, T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArrayOneRuns {
+            array: self.array,
+            range: self.range.clone(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> Iterator for ArrayOneRuns<'a, T, A> {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Range<usize>> {
+        next_run(self.array, &mut self.range, true)
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> DoubleEndedIterator for ArrayOneRuns<'a, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Range<usize>> {
+        next_run_back(self.array, &mut self.range, true)
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> FusedIterator for ArrayOneRuns<'a, T, A> {}
+
+/// An iterator over the maximal runs of `0`s of a bit array, from least to most significant.
+///
+/// Elements which are completely inside or outside of a run are skipped in a single step. This
+/// struct is created by [`BitArray::iter_zero_runs`].
+#[derive(Debug)]
+pub struct ArrayZeroRuns<'a, T: 'a, A: 'a + ?Sized = [T]> {
+    array: &'a A,
+    range: Range<usize>,
+    element: PhantomData<T>,
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> ArrayZeroRuns<'a, T, A> {
+    #[inline]
+    pub(crate) fn new(array: &'a A) -> Self {
+        ArrayZeroRuns {
+            array,
+            range: 0..array.bit_length(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T, A: ?Sized> Clone for ArrayZeroRuns<'a, T, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        ArrayZeroRuns {
+            array: self.array,
+            range: self.range.clone(),
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> Iterator for ArrayZeroRuns<'a, T, A> {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Range<usize>> {
+        next_run(self.array, &mut self.range, false)
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> DoubleEndedIterator for ArrayZeroRuns<'a, T, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Range<usize>> {
+        next_run_back(self.array, &mut self.range, false)
+    }
+}
+
+impl<'a, T: BitField, A: BitArray<T> + ?Sized> FusedIterator for ArrayZeroRuns<'a, T, A> {}
+
+/// Removes the first run of bits with the value `value` from `range` and returns it.
+#[inline]
+fn next_run<T, A>(array: &A, range: &mut Range<usize>, value: bool) -> Option<Range<usize>>
+where
+    T: BitField,
+    A: BitArray<T> + ?Sized,
+{
+    let (start, end) = if value {
+        let start = array.first_one_in(range.clone())?;
+        (start, array.first_zero_in(start..range.end))
+    } else {
+        let start = array.first_zero_in(range.clone())?;
+        (start, array.first_one_in(start..range.end))
+    };
+    let end = end.unwrap_or(range.end);
+    range.start = end;
+    Some(start..end)
+}
+
+/// Removes the last run of bits with the value `value` from `range` and returns it.
+#[inline]
+fn next_run_back<T, A>(array: &A, range: &mut Range<usize>, value: bool) -> Option<Range<usize>>
+where
+    T: BitField,
+    A: BitArray<T> + ?Sized,
+{
+    let (last, before) = if value {
+        let last = array.last_one_in(range.clone())?;
+        (last, array.last_zero_in(range.start..last))
+    } else {
+        let last = array.last_zero_in(range.clone())?;
+        (last, array.last_one_in(range.start..last))
+    };
+    let start = before.map_or(range.start, |bit| bit + 1);
+    range.end = start;
+    Some(start..last + 1)
+}
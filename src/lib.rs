@@ -3,11 +3,48 @@
 
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(test)]
 mod tests;
 
+#[macro_use]
+mod bounded;
+
+mod bit_arr;
+mod bit_slice;
+#[cfg(feature = "alloc")]
+mod bit_vec;
+mod bitfield;
+#[cfg(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2"))]
+mod bmi2;
+mod field;
+pub mod iter;
+
+pub use bit_arr::{elements_for, BitArr};
+pub use bit_slice::{BitSlice, BitSliceMut};
+#[cfg(feature = "alloc")]
+pub use bit_vec::BitVec;
+pub use field::Field;
+
+use core::convert::TryFrom;
+use core::fmt;
 use core::ops::{Bound, Range, RangeBounds};
 
+/// Items used by the expansion of the `bitfield!` macro, which are not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use bitfield::check_fields;
+    pub use core::fmt;
+    pub use core::{assert, concat, stringify};
+}
+
+use iter::{
+    ArrayBits, ArrayOneRuns, ArrayOnes, ArrayZeroRuns, ArrayZeros, Bits, OneRuns, Ones, ZeroRuns,
+    Zeros,
+};
+
 /// A generic trait which provides methods for extracting and setting specific bits or ranges of
 /// bits.
 pub trait BitField {
@@ -21,6 +58,17 @@ pub trait BitField {
     /// ```
     const BIT_LENGTH: usize;
 
+    /// The signed integer type with the same number of bits, which is used for sign-extended
+    /// fields by [`get_bits_signed`](BitField::get_bits_signed) and
+    /// [`set_bits_signed`](BitField::set_bits_signed).
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: <u16 as BitField>::Signed = -1i16;
+    /// ```
+    type Signed: BitField;
+
     /// Obtains the bit at the index `bit`; note that index 0 is the least significant bit, while
     /// index `length() - 1` is the most significant bit.
     ///
@@ -52,6 +100,15 @@ pub trait BitField {
     /// assert_eq!(value.get_bits(3..=3), value.get_bit(3) as u32);
     /// ```
     ///
+    /// The bits are never sign-extended, so for signed types the result is the raw bit pattern
+    /// of the range:
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// assert_eq!((-1i8).get_bits(0..3), 0b111);
+    /// ```
+    ///
     /// ## Panics
     ///
     /// This method will panic if the start or end indexes of the range are out of bounds of the
@@ -101,204 +158,3185 @@ pub trait BitField {
     /// assert_eq!(value, 0b1010);
     /// ```
     ///
+    /// For signed types, `value` may also be a negative number which fits into N bits in two's
+    /// complement representation:
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0i8;
+    ///
+    /// value.set_bits(0..4, -3);
+    /// assert_eq!(value, 0b1101);
+    ///
+    /// value.set_bits(4..8, 0b1111);
+    /// assert_eq!(value, -3);
+    /// ```
+    ///
     /// ## Panics
     ///
     /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
-    /// not in the lower N bits of `value`.
+    /// not in the lower N bits of `value` (unless `value` is a negative number which fits into N
+    /// bits).
     fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self;
-}
 
-pub trait BitArray<T: BitField> {
-    /// Returns the length, eg number of bits, in this bit array.
+    /// Returns a copy of `self` with the bit at the index `bit` set to the value `value`, see
+    /// [`set_bit`](BitField::set_bit).
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// assert_eq!([0u8, 4u8, 8u8].bit_length(), 24);
-    /// assert_eq!([0u32, 5u32].bit_length(), 64);
+    /// assert_eq!(0u32.with_bit(1, true), 2u32);
+    /// assert_eq!(0u32.with_bit(1, true).with_bit(3, true), 10u32);
+    /// assert_eq!(10u32.with_bit(1, false), 8u32);
     /// ```
-    fn bit_length(&self) -> usize;
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of the bounds of the bit field.
+    #[track_caller]
+    fn with_bit(mut self, bit: usize, value: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_bit(bit, value);
+        self
+    }
 
-    /// Obtains the bit at the index `bit`; note that index 0 is the least significant bit, while
-    /// index `length() - 1` is the most significant bit.
+    /// Returns a copy of `self` with the range of bits defined by the range `range` set to the
+    /// lower bits of `value`, see [`set_bits`](BitField::set_bits).
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let value: [u32; 1] = [0b110101];
+    /// const PRESENT: usize = 0;
+    /// const WRITABLE: usize = 1;
     ///
-    /// assert_eq!(value.get_bit(1), false);
-    /// assert_eq!(value.get_bit(2), true);
+    /// let entry = 0u64
+    ///     .with_bit(PRESENT, true)
+    ///     .with_bit(WRITABLE, true)
+    ///     .with_bits(12..52, 0x1234);
+    /// assert_eq!(entry, 0x1234_003);
     /// ```
     ///
     /// ## Panics
     ///
-    /// This method will panic if the bit index is out of bounds of the bit array.
-    fn get_bit(&self, bit: usize) -> bool;
+    /// This method will panic if the range is out of bounds of the bit field, or if there are `1`s
+    /// not in the lower N bits of `value`.
+    #[track_caller]
+    fn with_bits<T: RangeBounds<usize>>(mut self, range: T, value: Self) -> Self
+    where
+        Self: Sized,
+    {
+        self.set_bits(range, value);
+        self
+    }
 
-    /// Obtains the range of bits specified by `range`; note that index 0 is the least significant
-    /// bit, while index `length() - 1` is the most significant bit.
+    /// Returns a copy of `self` with all bits in the range `range` set to `0`.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
-    ///
-    /// let value: [u32; 2] = [0b110101, 0b11];
+    /// use bit_field::BitField;
     ///
-    /// assert_eq!(value.get_bits(0..3), 0b101);
-    /// assert_eq!(value.get_bits(..6), 0b110101);
-    /// assert_eq!(value.get_bits(31..33), 0b10);
-    /// assert_eq!(value.get_bits(5..=32), 0b1_0000_0000_0000_0000_0000_0000_001);
-    /// assert_eq!(value.get_bits(34..), 0);
+    /// assert_eq!(0xffu8.with_cleared_bits(2..6), 0b1100_0011);
+    /// assert_eq!(0xffu8.with_cleared_bits(..), 0);
     /// ```
     ///
     /// ## Panics
     ///
     /// This method will panic if the start or end indexes of the range are out of bounds of the
-    /// bit array, or if the range can't be contained by the bit field T.
-    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T;
+    /// bit field.
+    #[track_caller]
+    fn with_cleared_bits<T: RangeBounds<usize>>(mut self, range: T) -> Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        assert!(range.start < range.end);
 
-    /// Sets the bit at the index `bit` to the value `value` (where true means a value of '1' and
-    /// false means a value of '0'); note that index 0 is the least significant bit, while index
-    /// `length() - 1` is the most significant bit.
+        for bit in range {
+            self.set_bit(bit, false);
+        }
+        self
+    }
+
+    /// Returns a copy of `self` with the bit at the index `bit` inverted.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let mut value = [0u32];
+    /// assert_eq!(0b1010u8.with_toggled_bit(0), 0b1011);
+    /// assert_eq!(0b1010u8.with_toggled_bit(1), 0b1000);
+    /// ```
     ///
-    /// value.set_bit(1, true);
-    /// assert_eq!(value, [2u32]);
+    /// ## Panics
     ///
-    /// value.set_bit(3, true);
-    /// assert_eq!(value, [10u32]);
+    /// This method will panic if the bit index is out of the bounds of the bit field.
+    #[track_caller]
+    fn with_toggled_bit(mut self, bit: usize) -> Self
+    where
+        Self: Sized,
+    {
+        self.toggle_bit(bit);
+        self
+    }
+
+    /// Inverts the bit at the index `bit`; note that index 0 is the least significant bit, while
+    /// index `length() - 1` is the most significant bit.
     ///
-    /// value.set_bit(1, false);
-    /// assert_eq!(value, [8u32]);
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0b1010u8;
+    ///
+    /// value.toggle_bit(0);
+    /// assert_eq!(value, 0b1011);
+    ///
+    /// value.toggle_bit(1).toggle_bit(7);
+    /// assert_eq!(value, 0b1000_1001);
     /// ```
     ///
     /// ## Panics
     ///
-    /// This method will panic if the bit index is out of the bounds of the bit array.
-    fn set_bit(&mut self, bit: usize, value: bool);
+    /// This method will panic if the bit index is out of the bounds of the bit field.
+    #[track_caller]
+    fn toggle_bit(&mut self, bit: usize) -> &mut Self {
+        let value = self.get_bit(bit);
+        self.set_bit(bit, !value)
+    }
 
-    /// Sets the range of bits defined by the range `range` to the lower bits of `value`; to be
-    /// specific, if the range is N bits long, the N lower bits of `value` will be used; if any of
-    /// the other bits in `value` are set to 1, this function will panic.
+    /// Inverts all bits in the range `range`; note that index 0 is the least significant bit,
+    /// while index `length() - 1` is the most significant bit.
     ///
     /// ```rust
-    /// use bit_field::BitArray;
+    /// use bit_field::BitField;
     ///
-    /// let mut value = [0u32, 0u32];
+    /// let mut value = 0b1010u8;
     ///
-    /// value.set_bits(0..2, 0b11);
-    /// assert_eq!(value, [0b11, 0u32]);
+    /// value.toggle_bits(0..4);
+    /// assert_eq!(value, 0b0101);
     ///
-    /// value.set_bits(31..35, 0b1010);
-    /// assert_eq!(value, [0x0003, 0b101]);
+    /// value.toggle_bits(2..);
+    /// assert_eq!(value, 0b1111_1001);
     /// ```
     ///
     /// ## Panics
     ///
-    /// This method will panic if the range is out of bounds of the bit array,
-    /// if the range can't be contained by the bit field T, or if there are `1`s
-    /// not in the lower N bits of `value`.
-    fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: T);
-}
-
-/// An internal macro used for implementing BitField on the standard integral types.
-macro_rules! bitfield_numeric_impl {
-    ($($t:ty)*) => ($(
-        impl BitField for $t {
-            const BIT_LENGTH: usize = ::core::mem::size_of::<Self>() as usize * 8;
-
-            #[track_caller]
-            #[inline]
-            fn get_bit(&self, bit: usize) -> bool {
-                assert!(bit < Self::BIT_LENGTH);
-
-                (*self & (1 << bit)) != 0
-            }
-
-            #[track_caller]
-            #[inline]
-            fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self {
-                let range = to_regular_range(&range, Self::BIT_LENGTH);
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    #[track_caller]
+    fn toggle_bits<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        assert!(range.start < range.end);
 
-                assert!(range.start < Self::BIT_LENGTH);
-                assert!(range.end <= Self::BIT_LENGTH);
-                assert!(range.start < range.end);
+        for bit in range {
+            self.toggle_bit(bit);
+        }
+        self
+    }
 
-                // shift away high bits
-                let bits = *self << (Self::BIT_LENGTH - range.end) >> (Self::BIT_LENGTH - range.end);
+    /// Rotates the bits in the range `range` towards the more significant bits by `n` bits; the
+    /// most significant bits of the range wrap around to its least significant bits and the bits
+    /// outside of the range are left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0x00ab_cd00u32;
+    ///
+    /// value.rotate_bits_left(8..24, 4);
+    /// assert_eq!(value, 0x00bc_da00);
+    ///
+    /// value.rotate_bits_left(8..24, 20);
+    /// assert_eq!(value, 0x00cd_ab00);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit field.
+    #[track_caller]
+    fn rotate_bits_left<T: RangeBounds<usize>>(&mut self, range: T, n: usize) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        let bits = self.get_bits(range.clone()).raw_bits();
 
-                // shift away low bits
-                bits >> range.start
-            }
+        let len = range.len();
+        let n = n % len;
+        if n == 0 {
+            return self;
+        }
 
-            #[track_caller]
-            #[inline]
-            fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
-                assert!(bit < Self::BIT_LENGTH);
+        // the bits shifted out of the range have to be masked off
+        let bits = (bits << n | bits >> (len - n)) & (!0 >> (128 - len));
+        self.set_bits(range, Self::from_raw_bits(bits))
+    }
 
-                if value {
-                    *self |= 1 << bit;
-                } else {
-                    *self &= !(1 << bit);
-                }
+    /// Rotates the bits in the range `range` towards the less significant bits by `n` bits; the
+    /// least significant bits of the range wrap around to its most significant bits and the bits
+    /// outside of the range are left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0b1100_0110u8;
+    ///
+    /// value.rotate_bits_right(1..5, 1);
+    /// assert_eq!(value, 0b1101_0010);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit field.
+    #[track_caller]
+    fn rotate_bits_right<T: RangeBounds<usize>>(&mut self, range: T, n: usize) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        assert!(range.start < range.end);
 
-                self
-            }
+        let len = range.len();
+        self.rotate_bits_left(range, len - n % len)
+    }
 
-            #[track_caller]
-            #[inline]
-            fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self {
-                let range = to_regular_range(&range, Self::BIT_LENGTH);
+    /// Reverses the order of the bits in the range `range`, leaving the bits outside of the range
+    /// unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0b1100_0110u8;
+    ///
+    /// value.reverse_bits_in(0..4);
+    /// assert_eq!(value, 0b1100_0110);
+    ///
+    /// value.reverse_bits_in(2..8);
+    /// assert_eq!(value, 0b1000_1110);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit field.
+    #[track_caller]
+    fn reverse_bits_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        let bits = self.get_bits(range.clone()).raw_bits();
 
-                assert!(range.start < Self::BIT_LENGTH);
-                assert!(range.end <= Self::BIT_LENGTH);
-                assert!(range.start < range.end);
-                assert!(value << (Self::BIT_LENGTH - (range.end - range.start)) >>
-                        (Self::BIT_LENGTH - (range.end - range.start)) == value,
-                        "value does not fit into bit range");
+        let bits = bits.reverse_bits() >> (128 - range.len());
+        self.set_bits(range, Self::from_raw_bits(bits))
+    }
 
-                let bitmask: Self = !(!0 << (Self::BIT_LENGTH - range.end) >>
-                                    (Self::BIT_LENGTH - range.end) >>
-                                    range.start << range.start);
+    /// Reverses the order of the bytes in the range `range`, leaving the bits outside of the range
+    /// unchanged. The range doesn't need to be byte-aligned, but its length must be a multiple of
+    /// 8.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0x1234_5678u32;
+    ///
+    /// value.swap_bytes_in(8..32);
+    /// assert_eq!(value, 0x5634_1278);
+    ///
+    /// value.swap_bytes_in(4..20);
+    /// assert_eq!(value, 0x5632_7418);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit field, or if its
+    /// length is not a multiple of 8.
+    #[track_caller]
+    fn swap_bytes_in<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        let bits = self.get_bits(range.clone()).raw_bits();
 
-                // set bits
-                *self = (*self & bitmask) | (value << range.start);
+        assert!(range.len() % 8 == 0, "range length is not a multiple of 8");
 
-                self
-            }
-        }
-    )*)
-}
+        let bits = bits.swap_bytes() >> (128 - range.len());
+        self.set_bits(range, Self::from_raw_bits(bits))
+    }
 
-bitfield_numeric_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
+    /// Returns the number of `1`s in the range `range`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b0110_1100u8;
+    ///
+    /// assert_eq!(value.count_ones_in(2..6), 3);
+    /// assert_eq!(value.count_ones_in(..), 4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit field. Empty
+    /// ranges are accepted and return `0`.
+    #[track_caller]
+    fn count_ones_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_counted_range(&range, Self::BIT_LENGTH);
 
-impl<T: BitField> BitArray<T> for [T] {
-    #[inline]
-    fn bit_length(&self) -> usize {
-        self.len() * T::BIT_LENGTH
+        range.filter(|&bit| self.get_bit(bit)).count()
     }
 
+    /// Returns the number of `0`s in the range `range`.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b0110_1100u8;
+    ///
+    /// assert_eq!(value.count_zeros_in(2..6), 1);
+    /// assert_eq!(value.count_zeros_in(..), 4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit field. Empty
+    /// ranges are accepted and return `0`.
     #[track_caller]
-    #[inline]
-    fn get_bit(&self, bit: usize) -> bool {
-        let slice_index = bit / T::BIT_LENGTH;
-        let bit_index = bit % T::BIT_LENGTH;
-        self[slice_index].get_bit(bit_index)
+    fn count_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        range.len() - self.count_ones_in(range)
     }
 
+    /// Returns the number of leading `0`s in the range `range`, counted from the most significant
+    /// bit of the range (`range.end - 1`) downwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b0110_1100u8;
+    ///
+    /// assert_eq!(value.leading_zeros_in(2..7), 0);
+    /// assert_eq!(value.leading_zeros_in(..5), 1);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit field. Empty
+    /// ranges are accepted and return `0`.
     #[track_caller]
-    #[inline]
-    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T {
-        let range = to_regular_range(&range, self.bit_length());
+    fn leading_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_counted_range(&range, Self::BIT_LENGTH);
 
-        assert!(range.len() <= T::BIT_LENGTH);
+        range.rev().take_while(|&bit| !self.get_bit(bit)).count()
+    }
 
-        let slice_start = range.start / T::BIT_LENGTH;
-        let slice_end = range.end / T::BIT_LENGTH;
+    /// Returns the number of leading `1`s in the range `range`, counted from the most significant
+    /// bit of the range (`range.end - 1`) downwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b0110_1100u8;
+    ///
+    /// assert_eq!(value.leading_ones_in(2..7), 2);
+    /// assert_eq!(value.leading_ones_in(..), 0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit field. Empty
+    /// ranges are accepted and return `0`.
+    #[track_caller]
+    fn leading_ones_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_counted_range(&range, Self::BIT_LENGTH);
+
+        range.rev().take_while(|&bit| self.get_bit(bit)).count()
+    }
+
+    /// Returns the number of trailing `0`s in the range `range`, counted from the least
+    /// significant bit of the range (`range.start`) upwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b0110_1100u8;
+    ///
+    /// assert_eq!(value.trailing_zeros_in(1..), 1);
+    /// assert_eq!(value.trailing_zeros_in(4..5), 1);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit field. Empty
+    /// ranges are accepted and return `0`.
+    #[track_caller]
+    fn trailing_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_counted_range(&range, Self::BIT_LENGTH);
+
+        range.take_while(|&bit| !self.get_bit(bit)).count()
+    }
+
+    /// Returns the number of trailing `1`s in the range `range`, counted from the least
+    /// significant bit of the range (`range.start`) upwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b0110_1100u8;
+    ///
+    /// assert_eq!(value.trailing_ones_in(2..), 2);
+    /// assert_eq!(value.trailing_ones_in(5..7), 2);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit field. Empty
+    /// ranges are accepted and return `0`.
+    #[track_caller]
+    fn trailing_ones_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_counted_range(&range, Self::BIT_LENGTH);
+
+        range.take_while(|&bit| self.get_bit(bit)).count()
+    }
+
+    /// Returns an iterator over the indexes of the `1`s, from least to most significant.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1001_0100u8;
+    ///
+    /// assert!(value.iter_ones().eq([2, 4, 7].iter().cloned()));
+    /// assert_eq!(value.iter_ones().next_back(), Some(7));
+    /// assert_eq!(value.iter_ones().len(), 3);
+    /// ```
+    fn iter_ones(&self) -> Ones<Self>
+    where
+        Self: Sized,
+    {
+        Ones::new(self.get_bits(..))
+    }
+
+    /// Returns an iterator over the indexes of the `0`s, from least to most significant.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1001_0100u8;
+    ///
+    /// assert!(value.iter_zeros().eq([0, 1, 3, 5, 6].iter().cloned()));
+    /// assert_eq!(value.iter_zeros().len(), 5);
+    /// ```
+    fn iter_zeros(&self) -> Zeros<Self>
+    where
+        Self: Sized,
+    {
+        Zeros::new(self.get_bits(..))
+    }
+
+    /// Returns an iterator over all bits as `bool`s, from least to most significant.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1001_0100u8;
+    ///
+    /// assert_eq!(value.iter_bits().nth(2), Some(true));
+    /// assert_eq!(value.iter_bits().filter(|&bit| bit).count(), 3);
+    /// assert_eq!(value.iter_bits().len(), 8);
+    /// ```
+    fn iter_bits(&self) -> Bits<Self>
+    where
+        Self: Sized,
+    {
+        Bits::new(self.get_bits(..))
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive `1`s as ranges of bit indexes,
+    /// from least to most significant.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1100_1110u8;
+    ///
+    /// assert!(value.iter_one_runs().eq(vec![1..4, 6..8]));
+    /// assert_eq!(value.iter_one_runs().next_back(), Some(6..8));
+    /// ```
+    fn iter_one_runs(&self) -> OneRuns<Self>
+    where
+        Self: Sized,
+    {
+        OneRuns::new(self.get_bits(..))
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive `0`s as ranges of bit indexes,
+    /// from least to most significant.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1100_1110u8;
+    ///
+    /// assert!(value.iter_zero_runs().eq(vec![0..1, 4..6]));
+    /// ```
+    fn iter_zero_runs(&self) -> ZeroRuns<Self>
+    where
+        Self: Sized,
+    {
+        ZeroRuns::new(self.get_bits(..))
+    }
+
+    /// Obtains the range of bits specified by `range` as a two's complement number, which is
+    /// sign-extended to [`Self::Signed`](BitField::Signed); note that index 0 is the least
+    /// significant bit, while index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value: u32 = 0xabc0_0ffe;
+    ///
+    /// assert_eq!(value.get_bits_signed(0..12), -2);
+    /// assert_eq!(value.get_bits_signed(0..16), 0xffe);
+    /// assert_eq!(value.get_bits_signed(20..32), -0x544);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit field.
+    fn get_bits_signed<T: RangeBounds<usize>>(&self, range: T) -> Self::Signed;
+
+    /// Sets the range of bits defined by the range `range` to the two's complement representation
+    /// of `value`; to be specific, if the range is N bits long, `value` must be in the range
+    /// `-2^(N-1)..2^(N-1)`, otherwise this function will panic.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0u32;
+    ///
+    /// value.set_bits_signed(0..12, -2);
+    /// assert_eq!(value, 0xffe);
+    ///
+    /// value.set_bits_signed(12..16, 7);
+    /// assert_eq!(value, 0x7ffe);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if `value` does
+    /// not fit into N bits in two's complement representation.
+    fn set_bits_signed<T: RangeBounds<usize>>(
+        &mut self,
+        range: T,
+        value: Self::Signed,
+    ) -> &mut Self;
+
+    /// Obtains the value which is split into the pieces `ranges`, by concatenating the bits of
+    /// the pieces; the first piece provides the lowest bits of the value.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// // the base address of an x86 segment descriptor
+    /// let descriptor = 0x1200_0034_5678_0000u64;
+    ///
+    /// assert_eq!(descriptor.get_split_bits(&[16..40, 56..64]), 0x1234_5678);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if no pieces are given, if a piece is empty or out of bounds of the bit field, if the pieces
+    /// overlap, or if they are longer than the bit field in total.
+    #[track_caller]
+    fn get_split_bits(&self, ranges: &[Range<usize>]) -> Self
+    where
+        Self: Sized,
+    {
+        gather_split_bits(ranges, Self::BIT_LENGTH, |range| self.get_bits(range))
+    }
+
+    /// Splits the lower bits of `value` into the pieces `ranges`; the first piece receives the
+    /// lowest bits of the value. As with [`set_bits`](BitField::set_bits), if the pieces are N
+    /// bits long in total, only the N lower bits of `value` may be set, unless `value` is negative
+    /// and fits into N bits in two's complement representation.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut descriptor = 0u64;
+    ///
+    /// descriptor.set_split_bits(&[16..40, 56..64], 0x1234_5678);
+    /// descriptor.set_split_bits(&[0..16, 48..52], 0xf_ffff);
+    /// assert_eq!(descriptor, 0x120f_0034_5678_ffff);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if no pieces are given, if a piece is empty or out of bounds of the bit field, if the pieces
+    /// overlap, or if `value` does not fit into them.
+    #[track_caller]
+    fn set_split_bits(&mut self, ranges: &[Range<usize>], value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        scatter_split_bits(ranges, Self::BIT_LENGTH, value, |range, bits| {
+            self.set_bits(range, bits);
+        });
+        self
+    }
+
+    /// Obtains the range of bits specified by `range` and converts it into `E` with `TryFrom`,
+    /// which is typically an enum of the valid encodings of the field.
+    ///
+    /// ```rust
+    /// use bit_field::{BitField, InvalidFieldValue};
+    /// use std::convert::TryFrom;
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// enum PageSize {
+    ///     Small,
+    ///     Large,
+    /// }
+    ///
+    /// impl TryFrom<u32> for PageSize {
+    ///     type Error = ();
+    ///
+    ///     fn try_from(value: u32) -> Result<Self, ()> {
+    ///         match value {
+    ///             0b00 => Ok(PageSize::Small),
+    ///             0b01 => Ok(PageSize::Large),
+    ///             _ => Err(()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(0x0000_1000u32.get_field(12..14), Ok(PageSize::Large));
+    /// assert_eq!(
+    ///     0x0000_3000u32.get_field::<PageSize, _>(12..14),
+    ///     Err(InvalidFieldValue { value: 0b11, range: 12..14 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns an [`InvalidFieldValue`] with the bits and the range of the field if the conversion
+    /// fails.
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field.
+    #[track_caller]
+    fn get_field<E: TryFrom<Self>, T: RangeBounds<usize>>(
+        &self,
+        range: T,
+    ) -> Result<E, InvalidFieldValue<Self>>
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        // the bits are obtained again for the error, so that `Self` doesn't have to be `Clone`
+        E::try_from(self.get_bits(range.clone())).map_err(|_| InvalidFieldValue {
+            value: self.get_bits(range.clone()),
+            range,
+        })
+    }
+
+    /// Converts `value` into `Self` with `Into` and sets the range of bits specified by `range` to
+    /// the lower bits of the result, see [`set_bits`](BitField::set_bits).
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// #[derive(Clone, Copy)]
+    /// enum Privilege {
+    ///     Kernel = 0,
+    ///     User = 3,
+    /// }
+    ///
+    /// impl From<Privilege> for u16 {
+    ///     fn from(privilege: Privilege) -> u16 {
+    ///         privilege as u16
+    ///     }
+    /// }
+    ///
+    /// let mut selector = 0x0010u16;
+    ///
+    /// selector.set_field(0..2, Privilege::User);
+    /// assert_eq!(selector, 0x0013);
+    ///
+    /// selector.set_field(0..2, Privilege::Kernel);
+    /// assert_eq!(selector, 0x0010);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit field, or if the converted
+    /// value does not fit into the range.
+    #[track_caller]
+    fn set_field<E: Into<Self>, T: RangeBounds<usize>>(&mut self, range: T, value: E) -> &mut Self
+    where
+        Self: Sized,
+    {
+        self.set_bits(range, value.into())
+    }
+
+    /// Obtains the bit at the MSB-0 index `bit`, which counts the bits from the most significant
+    /// bit (index 0) downwards, as many network protocol and hardware specifications do.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1000_0010u8;
+    ///
+    /// assert_eq!(value.get_bit_msb0(0), true);
+    /// assert_eq!(value.get_bit_msb0(1), false);
+    /// assert_eq!(value.get_bit_msb0(6), true);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit field.
+    #[track_caller]
+    fn get_bit_msb0(&self, bit: usize) -> bool {
+        assert!(bit < Self::BIT_LENGTH);
+
+        self.get_bit(Self::BIT_LENGTH - 1 - bit)
+    }
+
+    /// Sets the bit at the MSB-0 index `bit` to the value `value`, see
+    /// [`get_bit_msb0`](BitField::get_bit_msb0).
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0u8;
+    ///
+    /// value.set_bit_msb0(0, true);
+    /// assert_eq!(value, 0b1000_0000);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit field.
+    #[track_caller]
+    fn set_bit_msb0(&mut self, bit: usize, value: bool) -> &mut Self {
+        assert!(bit < Self::BIT_LENGTH);
+
+        self.set_bit(Self::BIT_LENGTH - 1 - bit, value)
+    }
+
+    /// Obtains the range of bits specified by the MSB-0 range `range`, see
+    /// [`get_bit_msb0`](BitField::get_bit_msb0); the bit at `range.start` becomes the most
+    /// significant bit of the result.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0x1234_5678u32;
+    ///
+    /// assert_eq!(value.get_bits_msb0(0..8), 0x12);
+    /// assert_eq!(value.get_bits_msb0(4..16), 0x234);
+    /// assert_eq!(value.get_bits_msb0(28..), 0x8);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit field.
+    #[track_caller]
+    fn get_bits_msb0<T: RangeBounds<usize>>(&self, range: T) -> Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        // check the range before it is mirrored, so that reversed ranges can't wrap
+        assert!(range.start < range.end);
+        assert!(range.end <= Self::BIT_LENGTH);
+
+        self.get_bits(Self::BIT_LENGTH - range.end..Self::BIT_LENGTH - range.start)
+    }
+
+    /// Sets the range of bits specified by the MSB-0 range `range` to the lower bits of `value`,
+    /// see [`get_bits_msb0`](BitField::get_bits_msb0).
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0u16;
+    ///
+    /// value.set_bits_msb0(0..4, 0xa);
+    /// value.set_bits_msb0(12..16, 0x5);
+    /// assert_eq!(value, 0xa005);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit field, or if
+    /// `value` does not fit into the range.
+    #[track_caller]
+    fn set_bits_msb0<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+        assert!(range.start < range.end);
+        assert!(range.end <= Self::BIT_LENGTH);
+
+        self.set_bits(
+            Self::BIT_LENGTH - range.end..Self::BIT_LENGTH - range.start,
+            value,
+        )
+    }
+
+    /// Gathers the bits of `self` which are set in `mask` into the lower bits of the result, like
+    /// the BMI2 `pext` instruction; the bit at the lowest set bit of `mask` becomes bit `0`.
+    ///
+    /// With the `bmi2` feature, this uses the `pext` instruction on `x86_64` targets which enable
+    /// the `bmi2` target feature.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let value = 0b1011_0110u8;
+    ///
+    /// assert_eq!(value.extract_mask(0b0110_0110), 0b0111);
+    /// assert_eq!(value.extract_mask(0b1111_0000), 0b1011);
+    /// assert_eq!(value.extract_mask(0), 0);
+    /// ```
+    fn extract_mask(&self, mask: Self) -> Self
+    where
+        Self: Sized,
+    {
+        #[cfg(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2"))]
+        let bits = bmi2::extract_mask(self.raw_bits(), mask.raw_bits());
+        #[cfg(not(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2")))]
+        let bits = const_fn::u128::extract_mask(self.raw_bits(), mask.raw_bits());
+
+        Self::from_raw_bits(bits)
+    }
+
+    /// Scatters the lower bits of `value` to the bits of `self` which are set in `mask`, like the
+    /// BMI2 `pdep` instruction; bit `0` of `value` goes to the lowest set bit of `mask`. The bits
+    /// of `self` which are not set in `mask` are left unchanged and the bits of `value` above the
+    /// number of `1`s in `mask` are ignored.
+    ///
+    /// With the `bmi2` feature, this uses the `pdep` instruction on `x86_64` targets which enable
+    /// the `bmi2` target feature.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// let mut value = 0b1000_0001u8;
+    ///
+    /// value.deposit_mask(0b0110_0110, 0b1010);
+    /// assert_eq!(value, 0b1100_0101);
+    ///
+    /// // interleave the bits of two coordinates into a Morton code
+    /// let mut code = 0u16;
+    /// code.deposit_mask(0x5555, 0b1101).deposit_mask(0xaaaa, 0b0110);
+    /// assert_eq!(code, 0b0111_1001);
+    /// ```
+    fn deposit_mask(&mut self, mask: Self, value: Self) -> &mut Self
+    where
+        Self: Sized,
+    {
+        #[cfg(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2"))]
+        let bits = bmi2::deposit_mask(self.raw_bits(), mask.raw_bits(), value.raw_bits());
+        #[cfg(not(all(feature = "bmi2", target_arch = "x86_64", target_feature = "bmi2")))]
+        let bits = const_fn::u128::deposit_mask(self.raw_bits(), mask.raw_bits(), value.raw_bits());
+
+        *self = Self::from_raw_bits(bits);
+        self
+    }
+
+    /// Returns the bits of `self` zero-extended to a `u128`, regardless of whether `Self` is
+    /// signed; this is the common representation through which values of different widths are
+    /// converted into each other.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// assert_eq!(0xabu8.raw_bits(), 0xab);
+    /// assert_eq!((-1i16).raw_bits(), 0xffff);
+    /// ```
+    fn raw_bits(&self) -> u128 {
+        (0..Self::BIT_LENGTH)
+            .filter(|&bit| self.get_bit(bit))
+            .fold(0, |bits, bit| bits | 1 << bit)
+    }
+
+    /// Creates a value from the lower `BIT_LENGTH` bits of `bits`; the other bits are ignored.
+    ///
+    /// ```rust
+    /// use bit_field::BitField;
+    ///
+    /// assert_eq!(u8::from_raw_bits(0x1ab), 0xab);
+    /// assert_eq!(i16::from_raw_bits(0xffff), -1);
+    /// ```
+    fn from_raw_bits(bits: u128) -> Self
+    where
+        Self: Sized;
+
+    /// Fallible version of [`get_bit`](BitField::get_bit) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitField, BitFieldError};
+    ///
+    /// let value: u8 = 0b110101;
+    ///
+    /// assert_eq!(value.try_get_bit(2), Ok(true));
+    /// assert_eq!(
+    ///     value.try_get_bit(8),
+    ///     Err(BitFieldError::IndexOutOfBounds { index: 8, length: 8 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::IndexOutOfBounds`] if the bit index is out of bounds of the bit
+    /// field.
+    fn try_get_bit(&self, bit: usize) -> Result<bool, BitFieldError> {
+        check_index(bit, Self::BIT_LENGTH)?;
+
+        Ok(self.get_bit(bit))
+    }
+
+    /// Fallible version of [`get_bits`](BitField::get_bits) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitField, BitFieldError};
+    ///
+    /// let value: u8 = 0b110101;
+    ///
+    /// assert_eq!(value.try_get_bits(2..6), Ok(0b1101));
+    /// assert_eq!(
+    ///     value.try_get_bits(4..2),
+    ///     Err(BitFieldError::EmptyRange { start: 4, end: 2 })
+    /// );
+    /// assert_eq!(
+    ///     value.try_get_bits(4..9),
+    ///     Err(BitFieldError::IndexOutOfBounds { index: 9, length: 8 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::EmptyRange`] if the range is empty or reversed and
+    /// [`BitFieldError::IndexOutOfBounds`] if its end is out of bounds of the bit field.
+    fn try_get_bits<T: RangeBounds<usize>>(&self, range: T) -> Result<Self, BitFieldError>
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_range(&range, Self::BIT_LENGTH)?;
+
+        Ok(self.get_bits(range))
+    }
+
+    /// Fallible version of [`set_bit`](BitField::set_bit) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitField, BitFieldError};
+    ///
+    /// let mut value = 0u8;
+    ///
+    /// assert!(value.try_set_bit(3, true).is_ok());
+    /// assert_eq!(value, 0b1000);
+    /// assert_eq!(
+    ///     value.try_set_bit(8, true).err(),
+    ///     Some(BitFieldError::IndexOutOfBounds { index: 8, length: 8 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::IndexOutOfBounds`] if the bit index is out of bounds of the bit
+    /// field. The bit field is left unchanged in that case.
+    fn try_set_bit(&mut self, bit: usize, value: bool) -> Result<&mut Self, BitFieldError> {
+        check_index(bit, Self::BIT_LENGTH)?;
+
+        Ok(self.set_bit(bit, value))
+    }
+
+    /// Fallible version of [`set_bits`](BitField::set_bits) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitField, BitFieldError};
+    ///
+    /// let mut value = 0u8;
+    ///
+    /// assert!(value.try_set_bits(0..4, 0b1010).is_ok());
+    /// assert_eq!(value, 0b1010);
+    /// assert_eq!(
+    ///     value.try_set_bits(0..2, 0b100).err(),
+    ///     Some(BitFieldError::ValueOverflow)
+    /// );
+    /// assert_eq!(value, 0b1010);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::EmptyRange`] if the range is empty or reversed,
+    /// [`BitFieldError::IndexOutOfBounds`] if its end is out of bounds of the bit field and
+    /// [`BitFieldError::ValueOverflow`] if there are `1`s not in the lower N bits of `value`
+    /// (unless `value` is a negative number which fits into N bits). The bit field is left
+    /// unchanged in all of these cases.
+    ///
+    /// The default implementation doesn't know whether `Self` is signed, so it rejects negative
+    /// values as well.
+    fn try_set_bits<T: RangeBounds<usize>>(
+        &mut self,
+        range: T,
+        value: Self,
+    ) -> Result<&mut Self, BitFieldError>
+    where
+        Self: Sized,
+    {
+        let range = to_regular_range(&range, Self::BIT_LENGTH);
+        check_range(&range, Self::BIT_LENGTH)?;
+
+        if (range.len()..Self::BIT_LENGTH).any(|bit| value.get_bit(bit)) {
+            return Err(BitFieldError::ValueOverflow);
+        }
+
+        Ok(self.set_bits(range, value))
+    }
+}
+
+/// A trait for arrays of bit fields, which provides methods for extracting specific bits or
+/// ranges of bits across the elements. The methods which modify the bit array are provided by
+/// [`BitArrayMut`].
+///
+/// The ranges passed to [`get_bits`](BitArray::get_bits) must fit into a single element `T`. The
+/// other methods which take a range, like [`count_ones_in`](BitArray::count_ones_in) or
+/// [`first_one_in`](BitArray::first_one_in), accept ranges of any length spanning any number of
+/// elements and process them a whole element at a time.
+///
+/// Implementations only have to provide [`bit_length`](BitArray::bit_length),
+/// [`get_bit`](BitArray::get_bit), [`get_bits`](BitArray::get_bits) and
+/// [`bit_slice`](BitArray::bit_slice); the other methods are implemented in terms of them.
+pub trait BitArray<T: BitField> {
+    /// Returns the length, eg number of bits, in this bit array.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert_eq!([0u8, 4u8, 8u8].bit_length(), 24);
+    /// assert_eq!([0u32, 5u32].bit_length(), 64);
+    /// ```
+    fn bit_length(&self) -> usize;
+
+    /// Obtains the bit at the index `bit`; note that index 0 is the least significant bit, while
+    /// index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u32; 1] = [0b110101];
+    ///
+    /// assert_eq!(value.get_bit(1), false);
+    /// assert_eq!(value.get_bit(2), true);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    fn get_bit(&self, bit: usize) -> bool;
+
+    /// Obtains the range of bits specified by `range`; note that index 0 is the least significant
+    /// bit, while index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u32; 2] = [0b110101, 0b11];
+    ///
+    /// assert_eq!(value.get_bits(0..3), 0b101);
+    /// assert_eq!(value.get_bits(..6), 0b110101);
+    /// assert_eq!(value.get_bits(31..33), 0b10);
+    /// assert_eq!(value.get_bits(5..=32), 0b1_0000_0000_0000_0000_0000_0000_001);
+    /// assert_eq!(value.get_bits(34..), 0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit array, or if the range can't be contained by the bit field T.
+    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T;
+
+    /// Obtains the range of bits specified by `range` as a two's complement number, which is
+    /// sign-extended to `T::Signed`; note that index 0 is the least significant bit, while index
+    /// `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u8; 2] = [0b0110_0000, 0b1];
+    ///
+    /// assert_eq!(value.get_bits_signed(5..9), -5);
+    /// assert_eq!(value.get_bits_signed(5..8), 3);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the start or end indexes of the range are out of bounds of the
+    /// bit array, or if the range can't be contained by the bit field T.
+    #[track_caller]
+    fn get_bits_signed<U: RangeBounds<usize>>(&self, range: U) -> T::Signed {
+        let range = to_regular_range(&range, self.bit_length());
+        let len = range.len();
+
+        self.get_bits(range).get_bits_signed(0..len)
+    }
+
+    /// Obtains the range of bits specified by `range` as a value of the type `V`, which may be
+    /// wider than `T`; unlike [`get_bits`](BitArray::get_bits), the range may span any number of
+    /// elements, as long as it fits into `V`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value: [u8; 5] = [0x21, 0x43, 0x65, 0x87, 0xa9];
+    ///
+    /// assert_eq!(value.get_bits_as::<u32, _>(8..40), 0xa987_6543);
+    /// assert_eq!(value.get_bits_as::<u16, _>(4..16), 0x432);
+    /// assert_eq!(value.get_bits_as::<i16, _>(24..40), -0x5679);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit array, or if the
+    /// range can't be contained by `V`.
+    #[track_caller]
+    fn get_bits_as<V: BitField, U: RangeBounds<usize>>(&self, range: U) -> V {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start < range.end);
+        assert!(range.len() <= V::BIT_LENGTH);
+
+        let mut bits = 0u128;
+        for chunk in element_ranges(range.clone(), self.bit_length(), T::BIT_LENGTH) {
+            let offset = chunk.start - range.start;
+            bits |= self.get_bits(chunk).raw_bits() << offset;
+        }
+
+        V::from_raw_bits(bits)
+    }
+
+    /// Obtains the value of the type `V` which is split into the pieces `ranges`, by
+    /// concatenating the bits of the pieces; the first piece provides the lowest bits of the
+    /// value. As with [`get_bits_as`](BitArray::get_bits_as), the pieces may span any number of
+    /// elements.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let descriptor: [u32; 2] = [0x5678_ffff, 0x120f_0034];
+    ///
+    /// assert_eq!(descriptor.get_split_bits::<u32>(&[16..40, 56..64]), 0x1234_5678);
+    /// assert_eq!(descriptor.get_split_bits::<u32>(&[0..16, 48..52]), 0xf_ffff);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if no pieces are given, if a piece is empty or out of bounds of the bit array, if the pieces
+    /// overlap, or if they can't be contained by `V` in total.
+    #[track_caller]
+    #[inline]
+    fn get_split_bits<V: BitField>(&self, ranges: &[Range<usize>]) -> V {
+        gather_split_bits(ranges, self.bit_length(), |range| self.get_bits_as(range))
+    }
+
+    /// Obtains the bit at the MSB-0 index `bit`, which counts the bits of each element from its
+    /// most significant bit: the MSB-0 index `i` refers to the bit `T::BIT_LENGTH - 1 -
+    /// i % T::BIT_LENGTH` of the element `i / T::BIT_LENGTH`. For `[u8]`, this is the network bit
+    /// order used by the header diagrams of RFCs.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b1000_0000u8, 0b0000_0001];
+    ///
+    /// assert_eq!(value.get_bit_msb0(0), true);
+    /// assert_eq!(value.get_bit_msb0(7), false);
+    /// assert_eq!(value.get_bit_msb0(15), true);
+    /// ```
+    ///
+    /// If the [`bit_length`](BitArray::bit_length) is not a multiple of `T::BIT_LENGTH`, the bits
+    /// after the last complete element are counted from the last bit in bounds instead, so every
+    /// index below the bit length is valid:
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b0000_0000u8, 0b0000_1001];
+    /// let view = value.bit_slice(..12);
+    ///
+    /// assert_eq!(view.get_bit_msb0(8), true); // bit 11
+    /// assert_eq!(view.get_bit_msb0(11), true); // bit 8
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn get_bit_msb0(&self, bit: usize) -> bool {
+        self.get_bit(msb0_index(bit, self.bit_length(), T::BIT_LENGTH))
+    }
+
+    /// Obtains the range of bits specified by the MSB-0 range `range` as a value of the type `V`,
+    /// see [`get_bit_msb0`](BitArray::get_bit_msb0); the bit at `range.start` becomes the most
+    /// significant bit of the result. The range may span any number of elements, so fields can be
+    /// transcribed literally from header diagrams:
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// // the start of an IPv4 header
+    /// let header = [0x45u8, 0x00, 0x00, 0x54, 0xa6, 0xf2, 0x40, 0x00];
+    ///
+    /// assert_eq!(header.get_bits_msb0::<u8, _>(0..4), 4); // version
+    /// assert_eq!(header.get_bits_msb0::<u8, _>(4..8), 5); // header length
+    /// assert_eq!(header.get_bits_msb0::<u16, _>(16..32), 84); // total length
+    /// assert_eq!(header.get_bits_msb0::<u8, _>(48..51), 0b010); // flags
+    /// assert_eq!(header.get_bits_msb0::<u16, _>(51..64), 0); // fragment offset
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit array, or if the
+    /// range can't be contained by `V`.
+    #[track_caller]
+    #[inline]
+    fn get_bits_msb0<V: BitField, U: RangeBounds<usize>>(&self, range: U) -> V {
+        let range = to_regular_range(&range, self.bit_length());
+
+        gather_msb0_bits::<T, _, _>(range, self.bit_length(), |range| self.get_bits_as(range))
+    }
+
+    /// Returns the number of `1`s in the range `range`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0xf0u8, 0x0f, 0xf0];
+    ///
+    /// assert_eq!(value.count_ones_in(4..20), 8);
+    /// assert_eq!(value.count_ones_in(..), 12);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn count_ones_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+
+        element_ranges(range, self.bit_length(), T::BIT_LENGTH)
+            .map(|chunk| {
+                let len = chunk.len();
+                self.get_bits(chunk).count_ones_in(..len)
+            })
+            .sum()
+    }
+
+    /// Returns the number of `0`s in the range `range`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0xf0u8, 0x0f, 0xf0];
+    ///
+    /// assert_eq!(value.count_zeros_in(4..20), 8);
+    /// assert_eq!(value.count_zeros_in(..), 12);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn count_zeros_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+
+        element_ranges(range, self.bit_length(), T::BIT_LENGTH)
+            .map(|chunk| {
+                let len = chunk.len();
+                self.get_bits(chunk).count_zeros_in(..len)
+            })
+            .sum()
+    }
+
+    /// Returns the number of leading `0`s in the range `range`, counted from the most significant
+    /// bit of the range (`range.end - 1`) downwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0xf0u8, 0x0f, 0xf0];
+    ///
+    /// assert_eq!(value.leading_zeros_in(..16), 4);
+    /// assert_eq!(value.leading_zeros_in(4..10), 0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn leading_zeros_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+
+        let mut count = 0;
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH).rev() {
+            let len = chunk.len();
+            let bits = self.get_bits(chunk).leading_zeros_in(..len);
+            count += bits;
+            if bits < len {
+                break;
+            }
+        }
+        count
+    }
+
+    /// Returns the number of leading `1`s in the range `range`, counted from the most significant
+    /// bit of the range (`range.end - 1`) downwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0xf0u8, 0x0f, 0xf0];
+    ///
+    /// assert_eq!(value.leading_ones_in(..12), 8);
+    /// assert_eq!(value.leading_ones_in(..), 4);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn leading_ones_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+
+        let mut count = 0;
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH).rev() {
+            let len = chunk.len();
+            let bits = self.get_bits(chunk).leading_ones_in(..len);
+            count += bits;
+            if bits < len {
+                break;
+            }
+        }
+        count
+    }
+
+    /// Returns the number of trailing `0`s in the range `range`, counted from the least
+    /// significant bit of the range (`range.start`) upwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0xf0u8, 0x0f, 0xf0];
+    ///
+    /// assert_eq!(value.trailing_zeros_in(..), 4);
+    /// assert_eq!(value.trailing_zeros_in(12..), 8);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn trailing_zeros_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+
+        let mut count = 0;
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH) {
+            let len = chunk.len();
+            let bits = self.get_bits(chunk).trailing_zeros_in(..len);
+            count += bits;
+            if bits < len {
+                break;
+            }
+        }
+        count
+    }
+
+    /// Returns the number of trailing `1`s in the range `range`, counted from the least
+    /// significant bit of the range (`range.start`) upwards.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0xf0u8, 0x0f, 0xf0];
+    ///
+    /// assert_eq!(value.trailing_ones_in(4..), 8);
+    /// assert_eq!(value.trailing_ones_in(..4), 0);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn trailing_ones_in<U: RangeBounds<usize>>(&self, range: U) -> usize {
+        let range = to_regular_range(&range, self.bit_length());
+
+        let mut count = 0;
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH) {
+            let len = chunk.len();
+            let bits = self.get_bits(chunk).trailing_ones_in(..len);
+            count += bits;
+            if bits < len {
+                break;
+            }
+        }
+        count
+    }
+
+    /// Returns the index of the first (least significant) `1`, or `None` if all bits are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert_eq!([0u8, 0b0110_0000, 0b1].first_one(), Some(13));
+    /// assert_eq!([0u8, 0].first_one(), None);
+    /// ```
+    fn first_one(&self) -> Option<usize> {
+        self.first_one_in(..)
+    }
+
+    /// Returns the index of the first (least significant) `0`, or `None` if all bits are `1`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert_eq!([0xffu8, 0b1001_1111, 0b1].first_zero(), Some(13));
+    /// assert_eq!([0xffu8, 0xff].first_zero(), None);
+    /// ```
+    fn first_zero(&self) -> Option<usize> {
+        self.first_zero_in(..)
+    }
+
+    /// Returns the index of the last (most significant) `1`, or `None` if all bits are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert_eq!([0u8, 0b0110_0000, 0].last_one(), Some(14));
+    /// assert_eq!([0u8, 0].last_one(), None);
+    /// ```
+    fn last_one(&self) -> Option<usize> {
+        self.last_one_in(..)
+    }
+
+    /// Returns the index of the last (most significant) `0`, or `None` if all bits are `1`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert_eq!([0xffu8, 0b1001_1111, 0xff].last_zero(), Some(14));
+    /// assert_eq!([0xffu8, 0xff].last_zero(), None);
+    /// ```
+    fn last_zero(&self) -> Option<usize> {
+        self.last_zero_in(..)
+    }
+
+    /// Returns the index of the first `1` in the range `range`, or `None` if all bits in the range
+    /// are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b1000_0001u8, 0b1];
+    ///
+    /// assert_eq!(value.first_one_in(1..), Some(7));
+    /// assert_eq!(value.first_one_in(1..7), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn first_one_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        find_first(self, range, true)
+    }
+
+    /// Returns the index of the first `0` in the range `range`, or `None` if all bits in the range
+    /// are `1`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b0111_1110u8, 0b1];
+    ///
+    /// assert_eq!(value.first_zero_in(1..), Some(7));
+    /// assert_eq!(value.first_zero_in(1..7), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn first_zero_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        find_first(self, range, false)
+    }
+
+    /// Returns the index of the last `1` in the range `range`, or `None` if all bits in the range
+    /// are `0`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b1000_0001u8, 0b1];
+    ///
+    /// assert_eq!(value.last_one_in(..8), Some(7));
+    /// assert_eq!(value.last_one_in(1..7), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn last_one_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        find_last(self, range, true)
+    }
+
+    /// Returns the index of the last `0` in the range `range`, or `None` if all bits in the range
+    /// are `1`.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b0111_1110u8, 0b1];
+    ///
+    /// assert_eq!(value.last_zero_in(..8), Some(7));
+    /// assert_eq!(value.last_zero_in(1..7), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn last_zero_in<U: RangeBounds<usize>>(&self, range: U) -> Option<usize> {
+        let range = to_regular_range(&range, self.bit_length());
+
+        find_last(self, range, false)
+    }
+
+    /// Returns the index of the first `1` after the index `bit`, or `None` if there is none.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b1000_0001u8, 0b1];
+    ///
+    /// assert_eq!(value.next_one_after(0), Some(7));
+    /// assert_eq!(value.next_one_after(7), Some(8));
+    /// assert_eq!(value.next_one_after(8), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    #[track_caller]
+    fn next_one_after(&self, bit: usize) -> Option<usize> {
+        assert!(bit < self.bit_length());
+
+        self.first_one_in(bit + 1..)
+    }
+
+    /// Returns the index of the first `0` after the index `bit`, or `None` if there is none.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b0111_1110u8, 0b1111_1110];
+    ///
+    /// assert_eq!(value.next_zero_after(0), Some(7));
+    /// assert_eq!(value.next_zero_after(7), Some(8));
+    /// assert_eq!(value.next_zero_after(8), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    #[track_caller]
+    fn next_zero_after(&self, bit: usize) -> Option<usize> {
+        assert!(bit < self.bit_length());
+
+        self.first_zero_in(bit + 1..)
+    }
+
+    /// Returns the index of the last `1` before the index `bit`, or `None` if there is none.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b1000_0001u8, 0b1];
+    ///
+    /// assert_eq!(value.prev_one_before(8), Some(7));
+    /// assert_eq!(value.prev_one_before(7), Some(0));
+    /// assert_eq!(value.prev_one_before(0), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    #[track_caller]
+    fn prev_one_before(&self, bit: usize) -> Option<usize> {
+        assert!(bit < self.bit_length());
+
+        self.last_one_in(..bit)
+    }
+
+    /// Returns the index of the last `0` before the index `bit`, or `None` if there is none.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let value = [0b0111_1110u8, 0b1111_1110];
+    ///
+    /// assert_eq!(value.prev_zero_before(8), Some(7));
+    /// assert_eq!(value.prev_zero_before(7), Some(0));
+    /// assert_eq!(value.prev_zero_before(0), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    #[track_caller]
+    fn prev_zero_before(&self, bit: usize) -> Option<usize> {
+        assert!(bit < self.bit_length());
+
+        self.last_zero_in(..bit)
+    }
+
+    /// Returns the index of the first run of at least `len` consecutive `0`s which starts at a
+    /// multiple of `align`, or `None` if there is no such run.
+    ///
+    /// Use an `align` of `1` to accept runs at any position. The found run can be claimed with
+    /// [`set_range`](BitArrayMut::set_range).
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitArrayMut};
+    ///
+    /// let mut bitmap = [0b0000_0011u8, 0b1000_0000, 0];
+    ///
+    /// assert_eq!(bitmap.find_zero_run(5, 1), Some(2));
+    /// assert_eq!(bitmap.find_zero_run(5, 4), Some(4));
+    /// assert_eq!(bitmap.find_zero_run(10, 1), Some(2));
+    /// assert_eq!(bitmap.find_zero_run(10, 8), None);
+    ///
+    /// bitmap.set_range(4..9);
+    /// assert_eq!(bitmap.find_zero_run(5, 4), Some(16));
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `align` is `0`.
+    #[track_caller]
+    fn find_zero_run(&self, len: usize, align: usize) -> Option<usize> {
+        find_run(self, len, align, false)
+    }
+
+    /// Returns the index of the first run of at least `len` consecutive `1`s which starts at a
+    /// multiple of `align`, or `None` if there is no such run.
+    ///
+    /// Use an `align` of `1` to accept runs at any position. The found run can be released with
+    /// [`clear_range`](BitArrayMut::clear_range).
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0b1111_1100u8, 0b0111_1111, 0xff];
+    ///
+    /// assert_eq!(bitmap.find_one_run(5, 1), Some(2));
+    /// assert_eq!(bitmap.find_one_run(5, 4), Some(4));
+    /// assert_eq!(bitmap.find_one_run(8, 8), Some(16));
+    /// assert_eq!(bitmap.find_one_run(14, 1), None);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `align` is `0`.
+    #[track_caller]
+    fn find_one_run(&self, len: usize, align: usize) -> Option<usize> {
+        find_run(self, len, align, true)
+    }
+
+    /// Returns `true` if every bit that is set in the bit array is also set in `other`.
+    ///
+    /// If `other` is shorter, its missing bits are treated as `0`s.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert!([0b0010u8, 0].is_subset(&[0b0110][..]));
+    /// assert!(![0b0010u8, 1].is_subset(&[0b0110][..]));
+    /// ```
+    fn is_subset<A: BitArray<T> + ?Sized>(&self, other: &A) -> bool {
+        let len = other.bit_length().min(self.bit_length());
+
+        compare_with(self, other, |bits, other| bits & !other == 0)
+            && self.first_one_in(len..).is_none()
+    }
+
+    /// Returns `true` if every bit that is set in `other` is also set in the bit array.
+    ///
+    /// If the bit array is shorter, its missing bits are treated as `0`s.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert!([0b0110u8].is_superset(&[0b0010, 0][..]));
+    /// assert!(![0b0110u8].is_superset(&[0b0010, 1][..]));
+    /// ```
+    fn is_superset<A: BitArray<T> + ?Sized>(&self, other: &A) -> bool {
+        other.is_subset(self)
+    }
+
+    /// Returns `true` if no bit is set in both the bit array and `other`.
+    ///
+    /// Only the bits which exist in both bit arrays are compared.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert!([0b1001u8].is_disjoint(&[0b0110, 1][..]));
+    /// assert!(![0b1001u8].is_disjoint(&[0b0011][..]));
+    /// ```
+    fn is_disjoint<A: BitArray<T> + ?Sized>(&self, other: &A) -> bool {
+        compare_with(self, other, |bits, other| bits & other == 0)
+    }
+
+    /// Returns `true` if at least one bit is set in both the bit array and `other`; this is the
+    /// negation of [`is_disjoint`](BitArray::is_disjoint).
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert!([0b1001u8].intersects(&[0b0011][..]));
+    /// assert!(![0b1001u8].intersects(&[0b0110, 1][..]));
+    /// ```
+    fn intersects<A: BitArray<T> + ?Sized>(&self, other: &A) -> bool {
+        !self.is_disjoint(other)
+    }
+
+    /// Returns `true` if no bit is set, which means that the bit array is the empty set.
+    ///
+    /// This method isn't called `is_empty` because it would be shadowed by
+    /// [`<[T]>::is_empty`](slice::is_empty), which checks whether the slice has no elements.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert!([0u64, 0].is_clear());
+    /// assert!(![0u64, 1].is_clear());
+    /// ```
+    fn is_clear(&self) -> bool {
+        self.first_one().is_none()
+    }
+
+    /// Returns `true` if all bits are set.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// assert!([0xffu8, 0xff].is_full());
+    /// assert!(![0xffu8, 0x7f].is_full());
+    /// ```
+    fn is_full(&self) -> bool {
+        self.first_zero().is_none()
+    }
+
+    /// Returns a shared view of the range of bits specified by `range`, which is indexed relative
+    /// to the start of the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0x1234_5678u32, 0x9abc_def0];
+    /// let view = bitmap.bit_slice(28..36);
+    ///
+    /// assert_eq!(view.len(), 8);
+    /// assert_eq!(view.get_bits(..), 0x01);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    fn bit_slice<U: RangeBounds<usize>>(&self, range: U) -> BitSlice<'_, T>;
+
+    /// Returns an iterator over the indexes of the `1`s, from least to most significant.
+    ///
+    /// Elements without any `1`s are skipped in a single step.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0b1000_0001u8, 0, 0, 0b10];
+    ///
+    /// assert!(bitmap.iter_ones().eq([0, 7, 25].iter().cloned()));
+    /// assert_eq!(bitmap.iter_ones().next_back(), Some(25));
+    /// ```
+    fn iter_ones(&self) -> ArrayOnes<'_, T, Self> {
+        ArrayOnes::new(self)
+    }
+
+    /// Returns an iterator over the indexes of the `0`s, from least to most significant.
+    ///
+    /// Elements without any `0`s are skipped in a single step.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0xffu8, 0b1111_0111, 0xff];
+    ///
+    /// for bit in bitmap.iter_zeros() {
+    ///     assert_eq!(bit, 11);
+    /// }
+    /// ```
+    fn iter_zeros(&self) -> ArrayZeros<'_, T, Self> {
+        ArrayZeros::new(self)
+    }
+
+    /// Returns an iterator over all bits as `bool`s, from least to most significant.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0b0101u8, 0b1];
+    ///
+    /// assert_eq!(bitmap.iter_bits().len(), 16);
+    /// assert_eq!(bitmap.iter_bits().position(|bit| !bit), Some(1));
+    /// assert_eq!(bitmap.iter_bits().rposition(|bit| bit), Some(8));
+    /// ```
+    fn iter_bits(&self) -> ArrayBits<'_, T, Self> {
+        ArrayBits::new(self)
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive `1`s as ranges of bit indexes,
+    /// from least to most significant.
+    ///
+    /// Elements which are completely inside or outside of a run are skipped in a single step.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0xf0u8, 0xff, 0x01, 0x00, 0x80];
+    ///
+    /// assert!(bitmap.iter_one_runs().eq(vec![4..17, 39..40]));
+    /// ```
+    fn iter_one_runs(&self) -> ArrayOneRuns<'_, T, Self> {
+        ArrayOneRuns::new(self)
+    }
+
+    /// Returns an iterator over the maximal runs of consecutive `0`s as ranges of bit indexes,
+    /// from least to most significant.
+    ///
+    /// Elements which are completely inside or outside of a run are skipped in a single step.
+    ///
+    /// ```rust
+    /// use bit_field::BitArray;
+    ///
+    /// let bitmap = [0xf0u8, 0xff, 0x01, 0x00, 0x80];
+    ///
+    /// assert!(bitmap.iter_zero_runs().eq(vec![0..4, 17..39]));
+    /// ```
+    fn iter_zero_runs(&self) -> ArrayZeroRuns<'_, T, Self> {
+        ArrayZeroRuns::new(self)
+    }
+
+    /// Fallible version of [`get_bit`](BitArray::get_bit) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitFieldError};
+    ///
+    /// let value: [u8; 2] = [0b110101, 0b1];
+    ///
+    /// assert_eq!(value.try_get_bit(8), Ok(true));
+    /// assert_eq!(
+    ///     value.try_get_bit(16),
+    ///     Err(BitFieldError::IndexOutOfBounds { index: 16, length: 16 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::IndexOutOfBounds`] if the bit index is out of bounds of the bit
+    /// array.
+    fn try_get_bit(&self, bit: usize) -> Result<bool, BitFieldError> {
+        check_index(bit, self.bit_length())?;
+
+        Ok(self.get_bit(bit))
+    }
+
+    /// Fallible version of [`get_bits`](BitArray::get_bits) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitFieldError};
+    ///
+    /// let value: [u8; 2] = [0b110101, 0b1];
+    ///
+    /// assert_eq!(value.try_get_bits(4..10), Ok(0b10011));
+    /// assert_eq!(
+    ///     value.try_get_bits(0..10),
+    ///     Err(BitFieldError::RangeTooWide { length: 10, max: 8 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::EmptyRange`] if the range is empty or reversed,
+    /// [`BitFieldError::IndexOutOfBounds`] if its end is out of bounds of the bit array and
+    /// [`BitFieldError::RangeTooWide`] if the range can't be contained by the bit field T.
+    fn try_get_bits<U: RangeBounds<usize>>(&self, range: U) -> Result<T, BitFieldError> {
+        let range = to_regular_range(&range, self.bit_length());
+        check_range(&range, self.bit_length())?;
+        check_range_width(&range, T::BIT_LENGTH)?;
+
+        Ok(self.get_bits(range))
+    }
+}
+
+/// A trait for mutable arrays of bit fields, which extends [`BitArray`] with methods for setting
+/// specific bits or ranges of bits across the elements.
+///
+/// Like the ranges passed to [`BitArray::get_bits`], the ranges passed to
+/// [`set_bits`](BitArrayMut::set_bits) must fit into a single element `T`, while the other methods
+/// which take a range accept ranges of any length spanning any number of elements.
+///
+/// Implementations only have to provide [`set_bit`](BitArrayMut::set_bit),
+/// [`set_bits`](BitArrayMut::set_bits) and [`bit_slice_mut`](BitArrayMut::bit_slice_mut); the other
+/// methods are implemented in terms of them and the methods of [`BitArray`].
+pub trait BitArrayMut<T: BitField>: BitArray<T> {
+    /// Sets the bit at the index `bit` to the value `value` (where true means a value of '1' and
+    /// false means a value of '0'); note that index 0 is the least significant bit, while index
+    /// `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u32];
+    ///
+    /// value.set_bit(1, true);
+    /// assert_eq!(value, [2u32]);
+    ///
+    /// value.set_bit(3, true);
+    /// assert_eq!(value, [10u32]);
+    ///
+    /// value.set_bit(1, false);
+    /// assert_eq!(value, [8u32]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of the bounds of the bit array.
+    fn set_bit(&mut self, bit: usize, value: bool);
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value`; to be
+    /// specific, if the range is N bits long, the N lower bits of `value` will be used; if any of
+    /// the other bits in `value` are set to 1, this function will panic.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u32, 0u32];
+    ///
+    /// value.set_bits(0..2, 0b11);
+    /// assert_eq!(value, [0b11, 0u32]);
+    ///
+    /// value.set_bits(31..35, 0b1010);
+    /// assert_eq!(value, [0x0003, 0b101]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array,
+    /// if the range can't be contained by the bit field T, or if there are `1`s
+    /// not in the lower N bits of `value`.
+    fn set_bits<U: RangeBounds<usize>>(&mut self, range: U, value: T);
+
+    /// Sets the range of bits defined by the range `range` to the two's complement representation
+    /// of `value`; to be specific, if the range is N bits long, `value` must be in the range
+    /// `-2^(N-1)..2^(N-1)`, otherwise this function will panic.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u8, 0u8];
+    ///
+    /// value.set_bits_signed(6..10, -3);
+    /// assert_eq!(value, [0b0100_0000, 0b11]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is out of bounds of the bit array, if the range can't
+    /// be contained by the bit field T, or if `value` does not fit into N bits in two's complement
+    /// representation.
+    #[track_caller]
+    fn set_bits_signed<U: RangeBounds<usize>>(&mut self, range: U, value: T::Signed) {
+        let range = to_regular_range(&range, self.bit_length());
+        let len = range.len();
+
+        // the bits above `len` are zero, so only the range is overwritten
+        let mut bits = self.get_bits(range.clone());
+        bits.set_bits_signed(0..len, value);
+        self.set_bits(range, bits);
+    }
+
+    /// Sets the range of bits defined by the range `range` to the lower bits of `value`, which may
+    /// be wider than `T`; unlike [`set_bits`](BitArrayMut::set_bits), the range may span any number
+    /// of elements. As with [`set_bits`](BitArrayMut::set_bits), if the range is N bits long, only the
+    /// N lower bits of `value` may be set, unless `value` is negative and fits into N bits in two's
+    /// complement representation.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u8; 5];
+    ///
+    /// value.set_bits_from(8..40, 0xa987_6543u32);
+    /// assert_eq!(value, [0, 0x43, 0x65, 0x87, 0xa9]);
+    ///
+    /// value.set_bits_from(0..12, -2i16);
+    /// assert_eq!(value, [0xfe, 0x4f, 0x65, 0x87, 0xa9]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit array, if the
+    /// range can't be contained by `V`, or if `value` does not fit into the range. In these cases
+    /// the bit array is left unchanged.
+    #[track_caller]
+    fn set_bits_from<V: BitField, U: RangeBounds<usize>>(&mut self, range: U, value: V) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.start < range.end);
+        assert!(range.len() <= V::BIT_LENGTH);
+
+        // check that the value fits and strip the sign extension of negative values before any
+        // element is modified
+        let mut bits = value.get_bits(..);
+        bits.set_bits(0..range.len(), value);
+        let bits = bits.get_bits(0..range.len()).raw_bits();
+
+        for chunk in element_ranges(range.clone(), self.bit_length(), T::BIT_LENGTH) {
+            let offset = chunk.start - range.start;
+            let len = chunk.len();
+            self.set_bits(chunk, T::from_raw_bits(bits >> offset).get_bits(0..len));
+        }
+    }
+
+    /// Splits the lower bits of `value` into the pieces `ranges`; the first piece receives the
+    /// lowest bits of the value. As with [`set_bits_from`](BitArrayMut::set_bits_from), the pieces
+    /// may span any number of elements, and `value` must fit into their total length.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut descriptor = [0u32; 2];
+    ///
+    /// descriptor.set_split_bits(&[16..40, 56..64], 0x1234_5678u32);
+    /// assert_eq!(descriptor, [0x5678_0000, 0x1200_0034]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if no pieces are given, if a piece is empty or out of bounds of the bit array, if the pieces
+    /// overlap or can't be contained by `V` in total, or if `value` does not fit into them. In
+    /// these cases the bit array is left unchanged.
+    #[track_caller]
+    #[inline]
+    fn set_split_bits<V: BitField>(&mut self, ranges: &[Range<usize>], value: V) {
+        let bit_length = self.bit_length();
+        scatter_split_bits(ranges, bit_length, value, |range, bits| {
+            self.set_bits_from(range, bits)
+        });
+    }
+
+    /// Sets the bit at the MSB-0 index `bit` to the value `value`, see
+    /// [`get_bit_msb0`](BitArray::get_bit_msb0).
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u8; 2];
+    ///
+    /// value.set_bit_msb0(9, true);
+    /// assert_eq!(value, [0, 0b0100_0000]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn set_bit_msb0(&mut self, bit: usize, value: bool) {
+        let bit = msb0_index(bit, self.bit_length(), T::BIT_LENGTH);
+        self.set_bit(bit, value);
+    }
+
+    /// Sets the range of bits specified by the MSB-0 range `range` to the lower bits of `value`,
+    /// see [`get_bits_msb0`](BitArray::get_bits_msb0). As with
+    /// [`set_bits_from`](BitArrayMut::set_bits_from), `value` must fit into the range.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut header = [0u8; 4];
+    ///
+    /// header.set_bits_msb0(0..4, 6u8);
+    /// header.set_bits_msb0(12..32, 0xa_bcdeu32);
+    /// assert_eq!(header, [0x60, 0x0a, 0xbc, 0xde]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is empty or out of bounds of the bit array, if the
+    /// range can't be contained by `V`, or if `value` does not fit into the range. In these cases
+    /// the bit array is left unchanged.
+    #[track_caller]
+    #[inline]
+    fn set_bits_msb0<V: BitField, U: RangeBounds<usize>>(&mut self, range: U, value: V) {
+        let bit_length = self.bit_length();
+        let range = to_regular_range(&range, bit_length);
+
+        scatter_msb0_bits::<T, _, _>(range, bit_length, value, |range, bits| {
+            self.set_bits_from(range, bits)
+        });
+    }
+
+    /// Inverts the bit at the index `bit`; note that index 0 is the least significant bit, while
+    /// index `length() - 1` is the most significant bit.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u8, 0u8];
+    ///
+    /// value.toggle_bit(9);
+    /// assert_eq!(value, [0, 0b10]);
+    ///
+    /// value.toggle_bit(9);
+    /// assert_eq!(value, [0, 0]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the bit index is out of the bounds of the bit array.
+    #[track_caller]
+    fn toggle_bit(&mut self, bit: usize) {
+        let value = self.get_bit(bit);
+        self.set_bit(bit, !value);
+    }
+
+    /// Inverts all bits in the range `range`; note that index 0 is the least significant bit,
+    /// while index `length() - 1` is the most significant bit.
+    ///
+    /// Empty ranges are allowed and leave the bit array unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u8, 0u8, 0xffu8];
+    ///
+    /// value.toggle_bits(4..20);
+    /// assert_eq!(value, [0xf0, 0xff, 0xf0]);
+    ///
+    /// value.toggle_bits(..);
+    /// assert_eq!(value, [0x0f, 0x00, 0x0f]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn toggle_bits<U: RangeBounds<usize>>(&mut self, range: U) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH) {
+            let mut bits = self.get_bits(chunk.clone());
+            bits.toggle_bits(..chunk.len());
+            self.set_bits(chunk, bits);
+        }
+    }
+
+    /// Sets all bits in the range `range` to `1`.
+    ///
+    /// Empty ranges are allowed and leave the bit array unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0u8; 3];
+    ///
+    /// value.set_range(4..20);
+    /// assert_eq!(value, [0xf0, 0xff, 0x0f]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn set_range<U: RangeBounds<usize>>(&mut self, range: U) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH) {
+            let len = chunk.len();
+            let mut ones = self.get_bits(chunk.clone()).with_cleared_bits(..);
+            ones.toggle_bits(..len);
+            self.set_bits(chunk, ones);
+        }
+    }
+
+    /// Sets all bits in the range `range` to `0`.
+    ///
+    /// Empty ranges are allowed and leave the bit array unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0xffu8; 3];
+    ///
+    /// value.clear_range(4..20);
+    /// assert_eq!(value, [0x0f, 0x00, 0xf0]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    fn clear_range<U: RangeBounds<usize>>(&mut self, range: U) {
+        let range = to_regular_range(&range, self.bit_length());
+
+        for chunk in element_ranges(range, self.bit_length(), T::BIT_LENGTH) {
+            let zeros = self.get_bits(chunk.clone()).with_cleared_bits(..);
+            self.set_bits(chunk, zeros);
+        }
+    }
+
+    /// Copies the range of bits specified by `src_range` of `src` into this bit array, starting
+    /// at the index `dst_offset`.
+    ///
+    /// The source can be any bit array with the same element type, e.g. a [`BitSlice`]. Empty
+    /// ranges are allowed and leave the bit array unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArray, BitArrayMut};
+    ///
+    /// let mut dst = [0u8; 3];
+    ///
+    /// dst.copy_bits_from(4, &[0x34, 0x12][..], 4..16);
+    /// assert_eq!(dst, [0x30, 0x12, 0]);
+    ///
+    /// let src = [0xcdu8, 0xab];
+    /// dst.copy_bits_from(20, &src.bit_slice(4..8), ..);
+    /// assert_eq!(dst, [0x30, 0x12, 0xc0]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `src_range` is reversed or out of bounds of `src`, or if the
+    /// destination range is out of bounds of the bit array.
+    #[track_caller]
+    fn copy_bits_from<A: BitArray<T> + ?Sized, U: RangeBounds<usize>>(
+        &mut self,
+        dst_offset: usize,
+        src: &A,
+        src_range: U,
+    ) {
+        let src_range = to_regular_range(&src_range, src.bit_length());
+        assert!(src_range.start <= src_range.end);
+        assert!(src_range.end <= src.bit_length());
+
+        // an overflowing destination range is out of bounds as well
+        let dst_end = dst_offset.saturating_add(src_range.len());
+        for chunk in element_ranges(dst_offset..dst_end, self.bit_length(), T::BIT_LENGTH) {
+            let start = src_range.start + chunk.start - dst_offset;
+            let value = src.get_bits(start..start + chunk.len());
+            self.set_bits(chunk, value);
+        }
+    }
+
+    /// Moves the range of bits specified by `src_range` to the index `dst_offset` within the
+    /// bit array.
+    ///
+    /// The source and destination ranges may overlap; like `memmove`, the result is the same as
+    /// if the source bits were copied into a temporary buffer first. The bits of the source range
+    /// which aren't overwritten keep their value.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0x0fu8, 0];
+    ///
+    /// value.move_bits(0..8, 2);
+    /// assert_eq!(value, [0x3f, 0]);
+    ///
+    /// value.move_bits(2..10, 0);
+    /// assert_eq!(value, [0x0f, 0]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if `src_range` is reversed or out of bounds of the bit array, or if
+    /// the destination range is out of bounds of the bit array.
+    #[track_caller]
+    fn move_bits<U: RangeBounds<usize>>(&mut self, src_range: U, dst_offset: usize) {
+        let src_range = to_regular_range(&src_range, self.bit_length());
+        assert!(src_range.start <= src_range.end);
+        assert!(src_range.end <= self.bit_length());
+
+        // an overflowing destination range is out of bounds as well
+        let dst_end = dst_offset.saturating_add(src_range.len());
+        let chunks = element_ranges(dst_offset..dst_end, self.bit_length(), T::BIT_LENGTH);
+        let mut copy = |chunk: Range<usize>| {
+            let start = src_range.start + chunk.start - dst_offset;
+            let value = self.get_bits(start..start + chunk.len());
+            self.set_bits(chunk, value);
+        };
+
+        // copy in the direction which reads every source bit before it is overwritten
+        if dst_offset <= src_range.start {
+            chunks.for_each(&mut copy);
+        } else {
+            chunks.rev().for_each(&mut copy);
+        }
+    }
+
+    /// Shifts the whole bit array towards the more significant bits by `n` bits, as if it was one
+    /// big integer; the `n` least significant bits are filled with `0`s.
+    ///
+    /// Shifting by the bit length or more clears the bit array.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0x81u8, 0x01];
+    ///
+    /// value.shl_bits(4);
+    /// assert_eq!(value, [0x10, 0x18]);
+    /// ```
+    #[inline]
+    fn shl_bits(&mut self, n: usize) {
+        self.shl_bits_in(.., n);
+    }
+
+    /// Shifts the whole bit array towards the less significant bits by `n` bits, as if it was one
+    /// big integer; the `n` most significant bits are filled with `0`s.
+    ///
+    /// Shifting by the bit length or more clears the bit array.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0x81u8, 0x01];
+    ///
+    /// value.shr_bits(4);
+    /// assert_eq!(value, [0x18, 0x00]);
+    /// ```
+    #[inline]
+    fn shr_bits(&mut self, n: usize) {
+        self.shr_bits_in(.., n);
+    }
+
+    /// Rotates the whole bit array towards the more significant bits by `n` bits, as if it was
+    /// one big integer; the most significant bits wrap around to the least significant bits.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0x81u8, 0x01];
+    ///
+    /// value.rotate_left_bits(4);
+    /// assert_eq!(value, [0x10, 0x18]);
+    ///
+    /// value.rotate_left_bits(13);
+    /// assert_eq!(value, [0x02, 0x03]);
+    /// ```
+    #[inline]
+    fn rotate_left_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        rotate_range(self, 0..len, n);
+    }
+
+    /// Rotates the whole bit array towards the less significant bits by `n` bits, as if it was
+    /// one big integer; the least significant bits wrap around to the most significant bits.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0x81u8, 0x01];
+    ///
+    /// value.rotate_right_bits(4);
+    /// assert_eq!(value, [0x18, 0x10]);
+    /// ```
+    #[inline]
+    fn rotate_right_bits(&mut self, n: usize) {
+        let len = self.bit_length();
+        if len > 0 {
+            rotate_range(self, 0..len, len - n % len);
+        }
+    }
+
+    /// Shifts the range of bits specified by `range` towards the more significant bits by `n`
+    /// bits; the `n` least significant bits of the range are filled with `0`s and the bits
+    /// outside of the range are left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0xffu8, 0x01];
+    ///
+    /// value.shl_bits_in(4..12, 2);
+    /// assert_eq!(value, [0xcf, 0x07]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn shl_bits_in<U: RangeBounds<usize>>(&mut self, range: U, n: usize) {
+        let range = to_regular_range(&range, self.bit_length());
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        let n = n.min(range.len());
+        self.move_bits(range.start..range.end - n, range.start + n);
+        self.clear_range(range.start..range.start + n);
+    }
+
+    /// Shifts the range of bits specified by `range` towards the less significant bits by `n`
+    /// bits; the `n` most significant bits of the range are filled with `0`s and the bits outside
+    /// of the range are left unchanged.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut value = [0xffu8, 0x01];
+    ///
+    /// value.shr_bits_in(4..12, 2);
+    /// assert_eq!(value, [0x7f, 0x00]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    #[track_caller]
+    #[inline]
+    fn shr_bits_in<U: RangeBounds<usize>>(&mut self, range: U, n: usize) {
+        let range = to_regular_range(&range, self.bit_length());
+        assert!(range.start <= range.end);
+        assert!(range.end <= self.bit_length());
+
+        let n = n.min(range.len());
+        self.move_bits(range.start + n..range.end, range.start);
+        self.clear_range(range.end - n..range.end);
+    }
+
+    /// Sets every bit that is set in `other`, so that the bit array becomes the union of both
+    /// sets.
+    ///
+    /// If `other` is shorter, the remaining bits are left unchanged; if it is longer, its
+    /// remaining bits are ignored.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut cpus = [0b0011u64, 0];
+    ///
+    /// cpus.union_with(&[0b0110][..]);
+    /// assert_eq!(cpus, [0b0111, 0]);
+    /// ```
+    fn union_with<A: BitArray<T> + ?Sized>(&mut self, other: &A) {
+        combine_with(self, other, |bits, other| bits | other);
+    }
+
+    /// Clears every bit that is not set in `other`, so that the bit array becomes the
+    /// intersection of both sets.
+    ///
+    /// If `other` is shorter, its missing bits are treated as `0`s, so the remaining bits are
+    /// cleared; if it is longer, its remaining bits are ignored.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut cpus = [0b0011u64, 1];
+    ///
+    /// cpus.intersect_with(&[0b0110][..]);
+    /// assert_eq!(cpus, [0b0010, 0]);
+    /// ```
+    fn intersect_with<A: BitArray<T> + ?Sized>(&mut self, other: &A) {
+        combine_with(self, other, |bits, other| bits & other);
+
+        let len = other.bit_length();
+        if len < self.bit_length() {
+            self.clear_range(len..);
+        }
+    }
+
+    /// Clears every bit that is set in `other`, so that the bit array becomes the difference of
+    /// both sets.
+    ///
+    /// If `other` is shorter, the remaining bits are left unchanged; if it is longer, its
+    /// remaining bits are ignored.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut cpus = [0b0011u64, 1];
+    ///
+    /// cpus.difference_with(&[0b0110][..]);
+    /// assert_eq!(cpus, [0b0001, 1]);
+    /// ```
+    fn difference_with<A: BitArray<T> + ?Sized>(&mut self, other: &A) {
+        combine_with(self, other, |bits, other| bits & !other);
+    }
+
+    /// Inverts every bit that is set in `other`, so that the bit array becomes the symmetric
+    /// difference of both sets.
+    ///
+    /// If `other` is shorter, the remaining bits are left unchanged; if it is longer, its
+    /// remaining bits are ignored.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut cpus = [0b0011u64, 1];
+    ///
+    /// cpus.symmetric_difference_with(&[0b0110][..]);
+    /// assert_eq!(cpus, [0b0101, 1]);
+    /// ```
+    fn symmetric_difference_with<A: BitArray<T> + ?Sized>(&mut self, other: &A) {
+        combine_with(self, other, |bits, other| bits ^ other);
+    }
+
+    /// Inverts all bits, so that the bit array becomes the complement of the set.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut cpus = [0b0011u8, 0xf0];
+    ///
+    /// cpus.complement();
+    /// assert_eq!(cpus, [0b1111_1100, 0x0f]);
+    /// ```
+    fn complement(&mut self) {
+        self.toggle_bits(..);
+    }
+
+    /// Returns a mutable view of the range of bits specified by `range`, which is indexed relative
+    /// to the start of the range and never modifies the bits outside of it.
+    ///
+    /// ```rust
+    /// use bit_field::BitArrayMut;
+    ///
+    /// let mut bitmap = [0u32; 2];
+    /// bitmap.bit_slice_mut(28..36).set_bits(0..8, 0xab);
+    ///
+    /// assert_eq!(bitmap, [0xb000_0000, 0xa]);
+    /// ```
+    ///
+    /// ## Panics
+    ///
+    /// This method will panic if the range is reversed or out of bounds of the bit array.
+    fn bit_slice_mut<U: RangeBounds<usize>>(&mut self, range: U) -> BitSliceMut<'_, T>;
+
+    /// Fallible version of [`set_bit`](BitArrayMut::set_bit) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArrayMut, BitFieldError};
+    ///
+    /// let mut value = [0u8, 0u8];
+    ///
+    /// assert_eq!(value.try_set_bit(9, true), Ok(()));
+    /// assert_eq!(value, [0, 0b10]);
+    /// assert_eq!(
+    ///     value.try_set_bit(16, true),
+    ///     Err(BitFieldError::IndexOutOfBounds { index: 16, length: 16 })
+    /// );
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::IndexOutOfBounds`] if the bit index is out of bounds of the bit
+    /// array. The bit array is left unchanged in that case.
+    fn try_set_bit(&mut self, bit: usize, value: bool) -> Result<(), BitFieldError> {
+        check_index(bit, self.bit_length())?;
+
+        self.set_bit(bit, value);
+        Ok(())
+    }
+
+    /// Fallible version of [`set_bits`](BitArrayMut::set_bits) which returns an error instead of
+    /// panicking.
+    ///
+    /// ```rust
+    /// use bit_field::{BitArrayMut, BitFieldError};
+    ///
+    /// let mut value = [0u8, 0u8];
+    ///
+    /// assert_eq!(value.try_set_bits(6..10, 0b1111), Ok(()));
+    /// assert_eq!(value, [0b1100_0000, 0b11]);
+    /// assert_eq!(
+    ///     value.try_set_bits(6..10, 0b1_0000),
+    ///     Err(BitFieldError::ValueOverflow)
+    /// );
+    /// assert_eq!(value, [0b1100_0000, 0b11]);
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`BitFieldError::EmptyRange`] if the range is empty or reversed,
+    /// [`BitFieldError::IndexOutOfBounds`] if its end is out of bounds of the bit array,
+    /// [`BitFieldError::RangeTooWide`] if the range can't be contained by the bit field T and
+    /// [`BitFieldError::ValueOverflow`] if there are `1`s not in the lower N bits of `value`. The
+    /// bit array is left unchanged in all of these cases.
+    fn try_set_bits<U: RangeBounds<usize>>(
+        &mut self,
+        range: U,
+        value: T,
+    ) -> Result<(), BitFieldError> {
+        let range = to_regular_range(&range, self.bit_length());
+        check_range(&range, self.bit_length())?;
+        check_range_width(&range, T::BIT_LENGTH)?;
+
+        // validate the value before touching any element, so that a failure can't leave a
+        // partially written range behind
+        let mut scratch = self.get_bits(range.clone());
+        scratch.try_set_bits(0..range.len(), value.get_bits(..))?;
+
+        self.set_bits(range, value);
+        Ok(())
+    }
+}
+
+/// The error returned by the fallible `try_*` methods of [`BitField`] and [`BitArray`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BitFieldError {
+    /// A bit index, or the exclusive end of a range, lies outside of the bit field or bit array.
+    IndexOutOfBounds {
+        /// The offending bit index or range end.
+        index: usize,
+        /// The number of bits in the bit field or bit array.
+        length: usize,
+    },
+    /// The range contains no bits, because its start is not lower than its end.
+    EmptyRange {
+        /// The (inclusive) start of the range.
+        start: usize,
+        /// The (exclusive) end of the range.
+        end: usize,
+    },
+    /// The range is longer than the number of bits in a single element of the bit array.
+    RangeTooWide {
+        /// The number of bits in the range.
+        length: usize,
+        /// The number of bits in a single element.
+        max: usize,
+    },
+    /// The value has `1`s outside of the lower N bits, where N is the length of the range.
+    ValueOverflow,
+}
+
+impl fmt::Display for BitFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BitFieldError::IndexOutOfBounds { index, length } => write!(
+                f,
+                "bit index {} is out of bounds of a bit field of length {}",
+                index, length
+            ),
+            BitFieldError::EmptyRange { start, end } => {
+                write!(f, "bit range {}..{} is empty", start, end)
+            }
+            BitFieldError::RangeTooWide { length, max } => write!(
+                f,
+                "bit range of length {} does not fit into {} bits",
+                length, max
+            ),
+            BitFieldError::ValueOverflow => f.write_str("value does not fit into bit range"),
+        }
+    }
+}
+
+/// The error returned by [`BitField::get_field`] if the bits of a field don't encode a valid
+/// value of the requested type.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidFieldValue<T> {
+    /// The bits of the field, shifted to the lowest bits.
+    pub value: T,
+    /// The range of the field.
+    pub range: Range<usize>,
+}
+
+impl<T: fmt::LowerHex> fmt::Display for InvalidFieldValue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "invalid value {:#x} in bit range {}..{}",
+            self.value, self.range.start, self.range.end
+        )
+    }
+}
+
+/// An internal macro used for implementing BitField on the standard integral types.
+///
+/// Every type is paired with its unsigned counterpart, which is used for all shifts so that the
+/// bits of signed types are never sign-extended, and its signed counterpart, which is used for
+/// sign-extended fields.
+///
+/// Besides the `BitField` implementations, the macro generates the [`const_fn`] module, on which
+/// the implementations are built.
+macro_rules! bitfield_numeric_impl {
+    ($($t:ident: $u:ident, $s:ident);*) => (
+        $(
+        impl BitField for $t {
+            const BIT_LENGTH: usize = const_fn::$t::BIT_LENGTH;
+
+            type Signed = $s;
+
+            #[track_caller]
+            #[inline]
+            fn get_bit(&self, bit: usize) -> bool {
+                const_fn::$t::get_bit(*self, bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                const_fn::$t::get_bits(*self, range.start, range.end)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
+                *self = const_fn::$t::with_bit(*self, bit, value);
+
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                *self = const_fn::$t::with_bits(*self, range.start, range.end, value);
+
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn with_bit(self, bit: usize, value: bool) -> Self {
+                const_fn::$t::with_bit(self, bit, value)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn with_bits<T: RangeBounds<usize>>(self, range: T, value: Self) -> Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                const_fn::$t::with_bits(self, range.start, range.end, value)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn with_cleared_bits<T: RangeBounds<usize>>(self, range: T) -> Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                self & !const_fn::$t::mask(range.start, range.end)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn with_toggled_bit(self, bit: usize) -> Self {
+                assert!(bit < Self::BIT_LENGTH);
+
+                self ^ (1 << bit)
+            }
+
+            #[track_caller]
+            #[inline]
+            fn toggle_bit(&mut self, bit: usize) -> &mut Self {
+                *self = self.with_toggled_bit(bit);
+
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn toggle_bits<T: RangeBounds<usize>>(&mut self, range: T) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                *self ^= const_fn::$t::mask(range.start, range.end);
+
+                self
+            }
+
+            #[track_caller]
+            #[inline]
+            fn count_ones_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+                if !check_counted_range(&range, Self::BIT_LENGTH) {
+                    return 0;
+                }
+
+                self.get_bits(range).count_ones() as usize
+            }
+
+            #[track_caller]
+            #[inline]
+            fn leading_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+                if !check_counted_range(&range, Self::BIT_LENGTH) {
+                    return 0;
+                }
+                let bits = self.get_bits(range.clone()) as $u;
+
+                // the bits above the range are zero and must not be counted
+                bits.leading_zeros() as usize - (Self::BIT_LENGTH - range.len())
+            }
+
+            #[track_caller]
+            #[inline]
+            fn leading_ones_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+                if !check_counted_range(&range, Self::BIT_LENGTH) {
+                    return 0;
+                }
+                let bits = self.get_bits(range.clone()) as $u;
+
+                (bits << (Self::BIT_LENGTH - range.len())).leading_ones() as usize
+            }
+
+            #[track_caller]
+            #[inline]
+            fn trailing_zeros_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+                if !check_counted_range(&range, Self::BIT_LENGTH) {
+                    return 0;
+                }
+                let bits = self.get_bits(range.clone()) as $u;
+
+                (bits.trailing_zeros() as usize).min(range.len())
+            }
+
+            #[track_caller]
+            #[inline]
+            fn trailing_ones_in<T: RangeBounds<usize>>(&self, range: T) -> usize {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+                if !check_counted_range(&range, Self::BIT_LENGTH) {
+                    return 0;
+                }
+                let bits = self.get_bits(range) as $u;
+
+                bits.trailing_ones() as usize
+            }
+
+            #[track_caller]
+            #[inline]
+            fn get_bits_signed<T: RangeBounds<usize>>(&self, range: T) -> $s {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start < range.end);
+
+                // shift the sign bit of the range into the most significant bit, then shift it back
+                // with an arithmetic shift to sign-extend it
+                let bits = ((*self as $u) << (Self::BIT_LENGTH - range.end)) as $s;
+                bits >> (Self::BIT_LENGTH - (range.end - range.start))
+            }
+
+            #[track_caller]
+            #[inline]
+            fn set_bits_signed<T: RangeBounds<usize>>(&mut self, range: T, value: $s) -> &mut Self {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+
+                assert!(range.start < Self::BIT_LENGTH);
+                assert!(range.end <= Self::BIT_LENGTH);
+                assert!(range.start < range.end);
+
+                let len = range.end - range.start;
+                assert!(value << (Self::BIT_LENGTH - len) >> (Self::BIT_LENGTH - len) == value,
+                        "value does not fit into bit range");
+
+                // strip the sign extension
+                let bits = (value as $u) << (Self::BIT_LENGTH - len) >> (Self::BIT_LENGTH - len);
+                self.set_bits(range, bits as Self)
+            }
+
+            #[inline]
+            fn raw_bits(&self) -> u128 {
+                *self as $u as u128
+            }
+
+            #[inline]
+            fn from_raw_bits(bits: u128) -> Self {
+                bits as $u as Self
+            }
+
+            #[inline]
+            fn try_set_bits<T: RangeBounds<usize>>(
+                &mut self,
+                range: T,
+                value: Self,
+            ) -> Result<&mut Self, BitFieldError> {
+                let range = to_regular_range(&range, Self::BIT_LENGTH);
+                check_range(&range, Self::BIT_LENGTH)?;
+
+                if !const_fn::$t::fits(value, range.end - range.start) {
+                    return Err(BitFieldError::ValueOverflow);
+                }
+
+                Ok(self.set_bits(range, value))
+            }
+        }
+        )*
+
+        /// Inherent `const fn` counterparts of the [`BitField`] methods, which can be used to
+        /// build constants and statics.
+        ///
+        /// There is one module per integral type. Instead of a [`RangeBounds`], the functions take
+        /// the start (inclusive) and end (exclusive) of the range as separate arguments; otherwise
+        /// they behave exactly like their [`BitField`] counterparts, including the panics, which
+        /// become compile errors when the functions are evaluated in a const context.
+        ///
+        /// ```rust
+        /// use bit_field::const_fn;
+        ///
+        /// const PRESENT: usize = 0;
+        /// const WRITABLE: usize = 1;
+        ///
+        /// static TEMPLATE: u64 = const_fn::u64::with_bits(
+        ///     const_fn::u64::with_bit(const_fn::u64::with_bit(0, PRESENT, true), WRITABLE, true),
+        ///     12,
+        ///     52,
+        ///     0x1234,
+        /// );
+        ///
+        /// assert_eq!(TEMPLATE, 0x1234_003);
+        /// assert_eq!(const_fn::u64::get_bits(TEMPLATE, 12, 52), 0x1234);
+        /// assert_eq!(const_fn::u64::mask(12, 52), 0x000f_ffff_ffff_f000);
+        /// ```
+        ///
+        /// Invalid arguments are detected at compile time:
+        ///
+        /// ```compile_fail
+        /// use bit_field::const_fn;
+        ///
+        /// const VALUE: u8 = const_fn::u8::with_bits(0, 4, 8, 0x1f);
+        /// ```
+        pub mod const_fn {
+            $(
+            #[doc = concat!("`const fn` bit manipulation functions for `", stringify!($t), "`.")]
+            pub mod $t {
+                /// The number of bits in the type, see [`BitField::BIT_LENGTH`](::BitField::BIT_LENGTH).
+                pub const BIT_LENGTH: usize = ::core::mem::size_of::<$t>() * 8;
+
+                /// Obtains the bit at the index `bit`, see [`BitField::get_bit`](::BitField::get_bit).
+                ///
+                /// ## Panics
+                ///
+                /// This function will panic if the bit index is out of bounds of the bit field.
+                #[track_caller]
+                #[inline]
+                pub const fn get_bit(value: $t, bit: usize) -> bool {
+                    assert!(bit < BIT_LENGTH);
+
+                    (value & (1 << bit)) != 0
+                }
+
+                /// Obtains the bits in the range `start..end`, see
+                /// [`BitField::get_bits`](::BitField::get_bits).
+                ///
+                /// ## Panics
+                ///
+                /// This function will panic if the start or end indexes of the range are out of
+                /// bounds of the bit field.
+                #[track_caller]
+                #[inline]
+                pub const fn get_bits(value: $t, start: usize, end: usize) -> $t {
+                    assert!(start < BIT_LENGTH);
+                    assert!(end <= BIT_LENGTH);
+                    assert!(start < end);
+
+                    // shift away high bits
+                    let bits = (value as $u) << (BIT_LENGTH - end) >> (BIT_LENGTH - end);
+
+                    // shift away low bits
+                    (bits >> start) as $t
+                }
+
+                /// Returns `value` with the bit at the index `bit` set to `bit_value`, see
+                /// [`BitField::set_bit`](::BitField::set_bit).
+                ///
+                /// ## Panics
+                ///
+                /// This function will panic if the bit index is out of bounds of the bit field.
+                #[track_caller]
+                #[inline]
+                pub const fn with_bit(value: $t, bit: usize, bit_value: bool) -> $t {
+                    assert!(bit < BIT_LENGTH);
+
+                    if bit_value {
+                        value | (1 << bit)
+                    } else {
+                        value & !(1 << bit)
+                    }
+                }
+
+                /// Returns `value` with the bits in the range `start..end` set to the lower bits
+                /// of `bits`, see [`BitField::set_bits`](::BitField::set_bits).
+                ///
+                /// ## Panics
+                ///
+                /// This function will panic if the range is out of bounds of the bit field, or if
+                /// `bits` does not fit into the range.
+                #[track_caller]
+                #[inline]
+                pub const fn with_bits(value: $t, start: usize, end: usize, bits: $t) -> $t {
+                    assert!(start < BIT_LENGTH);
+                    assert!(end <= BIT_LENGTH);
+                    assert!(start < end);
+                    assert!(fits(bits, end - start), "value does not fit into bit range");
+
+                    let len = end - start;
+                    let bits = (bits as $u) << (BIT_LENGTH - len) >> (BIT_LENGTH - len);
+
+                    ((value as $u & !(mask(start, end) as $u)) | (bits << start)) as $t
+                }
+
+                /// Returns a value with all bits in the range `start..end` set to `1` and all
+                /// other bits set to `0`.
+                ///
+                /// ## Panics
+                ///
+                /// This function will panic if the start or end indexes of the range are out of
+                /// bounds of the bit field.
+                #[track_caller]
+                #[inline]
+                pub const fn mask(start: usize, end: usize) -> $t {
+                    assert!(start < BIT_LENGTH);
+                    assert!(end <= BIT_LENGTH);
+                    assert!(start < end);
+
+                    (<$u>::MAX >> (BIT_LENGTH - (end - start)) << start) as $t
+                }
+
+                /// Gathers the bits of `value` which are set in `mask` into the lower bits of the
+                /// result, see [`BitField::extract_mask`](::BitField::extract_mask).
+                ///
+                /// This is the portable implementation, which never uses the BMI2 instructions.
+                #[inline]
+                pub const fn extract_mask(value: $t, mask: $t) -> $t {
+                    let (value, mut mask) = (value as $u, mask as $u);
+
+                    let mut bits: $u = 0;
+                    let mut bit = 0;
+                    while mask != 0 {
+                        let lowest = mask & mask.wrapping_neg();
+                        if value & lowest != 0 {
+                            bits |= 1 << bit;
+                        }
+                        bit += 1;
+                        mask ^= lowest;
+                    }
+                    bits as $t
+                }
+
+                /// Returns `value` with the lower bits of `bits` scattered to the bits which are
+                /// set in `mask`, see [`BitField::deposit_mask`](::BitField::deposit_mask).
+                ///
+                /// This is the portable implementation, which never uses the BMI2 instructions.
+                #[inline]
+                pub const fn deposit_mask(value: $t, mask: $t, bits: $t) -> $t {
+                    let (mut mask, mut bits) = (mask as $u, bits as $u);
+
+                    let mut value = value as $u & !mask;
+                    while mask != 0 {
+                        let lowest = mask & mask.wrapping_neg();
+                        if bits & 1 != 0 {
+                            value |= lowest;
+                        }
+                        bits >>= 1;
+                        mask ^= lowest;
+                    }
+                    value as $t
+                }
+
+                /// Checks whether `value` fits into a range of `len` bits: all unused bits must
+                /// either be `0` or, for negative values of signed types, copies of the sign bit
+                /// of the range.
+                #[inline]
+                pub(crate) const fn fits(value: $t, len: usize) -> bool {
+                    let bits = value as $u;
+
+                    bits << (BIT_LENGTH - len) >> (BIT_LENGTH - len) == bits
+                        || ($t::MIN != 0
+                            && (bits << (BIT_LENGTH - len)) as $t >> (BIT_LENGTH - len) == value)
+                }
+            }
+            )*
+        }
+    )
+}
+
+bitfield_numeric_impl! {
+    u8: u8, i8; u16: u16, i16; u32: u32, i32; u64: u64, i64; u128: u128, i128; usize: usize, isize;
+    i8: u8, i8; i16: u16, i16; i32: u32, i32; i64: u64, i64; i128: u128, i128; isize: usize, isize
+}
+
+impl<T: BitField> BitArray<T> for [T] {
+    #[inline]
+    fn bit_length(&self) -> usize {
+        self.len() * T::BIT_LENGTH
+    }
+
+    #[track_caller]
+    #[inline]
+    fn get_bit(&self, bit: usize) -> bool {
+        let slice_index = bit / T::BIT_LENGTH;
+        let bit_index = bit % T::BIT_LENGTH;
+        self[slice_index].get_bit(bit_index)
+    }
+
+    #[track_caller]
+    #[inline]
+    fn get_bits<U: RangeBounds<usize>>(&self, range: U) -> T {
+        let range = to_regular_range(&range, self.bit_length());
+
+        assert!(range.len() <= T::BIT_LENGTH);
+
+        let slice_start = range.start / T::BIT_LENGTH;
+        let slice_end = range.end / T::BIT_LENGTH;
         let bit_start = range.start % T::BIT_LENGTH;
         let bit_end = range.end % T::BIT_LENGTH;
         let len = range.len();
@@ -319,6 +3357,14 @@ impl<T: BitField> BitArray<T> for [T] {
         }
     }
 
+    #[track_caller]
+    #[inline]
+    fn bit_slice<U: RangeBounds<usize>>(&self, range: U) -> BitSlice<'_, T> {
+        BitSlice::new(self, range)
+    }
+}
+
+impl<T: BitField> BitArrayMut<T> for [T] {
     #[track_caller]
     #[inline]
     fn set_bit(&mut self, bit: usize, value: bool) {
@@ -346,30 +3392,470 @@ impl<T: BitField> BitArray<T> for [T] {
         } else if bit_end == 0 {
             self[slice_start].set_bits(bit_start..T::BIT_LENGTH, value);
         } else {
+            // check that the value fits and strip the sign extension of negative values
+            let mut bits = value.get_bits(..);
+            bits.set_bits(0..range.len(), value);
+            let bits = bits.get_bits(0..range.len());
+
             self[slice_start].set_bits(
                 bit_start..T::BIT_LENGTH,
-                value.get_bits(0..T::BIT_LENGTH - bit_start),
+                bits.get_bits(0..T::BIT_LENGTH - bit_start),
             );
             self[slice_end].set_bits(
                 0..bit_end,
-                value.get_bits(T::BIT_LENGTH - bit_start..T::BIT_LENGTH),
+                bits.get_bits(T::BIT_LENGTH - bit_start..range.len()),
             );
         }
     }
+
+    #[track_caller]
+    #[inline]
+    fn bit_slice_mut<U: RangeBounds<usize>>(&mut self, range: U) -> BitSliceMut<'_, T> {
+        BitSliceMut::new(self, range)
+    }
+}
+
+/// Replaces the bits of `array` which also exist in `other` with `op(bits, other_bits)`, one
+/// element of `array` at a time.
+#[inline]
+fn combine_with<T, A, B, F>(array: &mut A, other: &B, op: F)
+where
+    T: BitField,
+    A: BitArrayMut<T> + ?Sized,
+    B: BitArray<T> + ?Sized,
+    F: Fn(u128, u128) -> u128,
+{
+    let len = array.bit_length().min(other.bit_length());
+
+    for chunk in element_ranges(0..len, array.bit_length(), T::BIT_LENGTH) {
+        let bits = op(
+            array.get_bits(chunk.clone()).raw_bits(),
+            other.get_bits(chunk.clone()).raw_bits(),
+        );
+        let len = chunk.len();
+        array.set_bits(chunk, T::from_raw_bits(bits).get_bits(0..len));
+    }
+}
+
+/// Returns `true` if `predicate(bits, other_bits)` holds for the bits of `array` which also exist
+/// in `other`, one element of `array` at a time.
+#[inline]
+fn compare_with<T, A, B, F>(array: &A, other: &B, predicate: F) -> bool
+where
+    T: BitField,
+    A: BitArray<T> + ?Sized,
+    B: BitArray<T> + ?Sized,
+    F: Fn(u128, u128) -> bool,
+{
+    let len = array.bit_length().min(other.bit_length());
+
+    element_ranges(0..len, array.bit_length(), T::BIT_LENGTH).all(|chunk| {
+        predicate(
+            array.get_bits(chunk.clone()).raw_bits(),
+            other.get_bits(chunk).raw_bits(),
+        )
+    })
+}
+
+/// Concatenates the bits of the pieces `ranges` of a bit array with `bit_length` bits, which are
+/// obtained with `get_bits`, into a value of the type `V`.
+#[track_caller]
+#[inline]
+fn gather_split_bits<V, F>(ranges: &[Range<usize>], bit_length: usize, get_bits: F) -> V
+where
+    V: BitField,
+    F: Fn(Range<usize>) -> V,
+{
+    check_split_ranges(ranges, bit_length, V::BIT_LENGTH);
+
+    let mut bits = 0u128;
+    let mut offset = 0;
+    for range in ranges {
+        bits |= get_bits(range.clone()).raw_bits() << offset;
+        offset += range.len();
+    }
+    V::from_raw_bits(bits)
+}
+
+/// Splits `value` into the pieces `ranges` of a bit array with `bit_length` bits, which are
+/// written with `set_bits`.
+#[track_caller]
+#[inline]
+fn scatter_split_bits<V, F>(ranges: &[Range<usize>], bit_length: usize, value: V, mut set_bits: F)
+where
+    V: BitField,
+    F: FnMut(Range<usize>, V),
+{
+    let len = check_split_ranges(ranges, bit_length, V::BIT_LENGTH);
+
+    // check that the value fits and strip the sign extension of negative values before any
+    // element is modified
+    let mut bits = value.get_bits(..);
+    bits.set_bits(0..len, value);
+
+    let mut offset = 0;
+    for range in ranges {
+        set_bits(range.clone(), bits.get_bits(offset..offset + range.len()));
+        offset += range.len();
+    }
+}
+
+/// Checks that `ranges` is a non-empty list of non-empty, disjoint ranges of a bit array with
+/// `bit_length` bits which are at most `max` bits long in total, and returns their total length.
+#[track_caller]
+#[inline]
+fn check_split_ranges(ranges: &[Range<usize>], bit_length: usize, max: usize) -> usize {
+    assert!(!ranges.is_empty(), "no bit ranges given");
+
+    let mut len = 0;
+    for (index, range) in ranges.iter().enumerate() {
+        assert!(range.start < range.end, "bit range is empty or reversed");
+        assert!(range.end <= bit_length, "bit range is out of bounds");
+        assert!(
+            ranges[..index]
+                .iter()
+                .all(|other| range.end <= other.start || other.end <= range.start),
+            "bit ranges overlap"
+        );
+        len += range.len();
+    }
+    assert!(len <= max, "bit ranges are too long for the value");
+
+    len
+}
+
+/// Implements [`BitArray::get_bits_msb0`] for a bit array of `bit_length` bits, whose LSB-0
+/// pieces are obtained with `get_bits`.
+#[track_caller]
+#[inline]
+fn gather_msb0_bits<T, V, F>(range: Range<usize>, bit_length: usize, get_bits: F) -> V
+where
+    T: BitField,
+    V: BitField,
+    F: Fn(Range<usize>) -> V,
+{
+    assert!(range.start < range.end);
+    assert!(range.len() <= V::BIT_LENGTH);
+
+    // the piece in the last element provides the lowest bits
+    let mut bits = 0u128;
+    let mut offset = 0;
+    for piece in msb0_ranges(range, bit_length, T::BIT_LENGTH).rev() {
+        bits |= get_bits(piece.clone()).raw_bits() << offset;
+        offset += piece.len();
+    }
+    V::from_raw_bits(bits)
+}
+
+/// Implements [`BitArrayMut::set_bits_msb0`] for a bit array of `bit_length` bits, whose LSB-0
+/// pieces are set with `set_bits`.
+#[track_caller]
+#[inline]
+fn scatter_msb0_bits<T, V, F>(range: Range<usize>, bit_length: usize, value: V, mut set_bits: F)
+where
+    T: BitField,
+    V: BitField,
+    F: FnMut(Range<usize>, V),
+{
+    assert!(range.start < range.end);
+    assert!(range.len() <= V::BIT_LENGTH);
+
+    // check that the value fits and strip the sign extension of negative values before any
+    // element is modified
+    let mut bits = value.get_bits(..);
+    bits.set_bits(0..range.len(), value);
+
+    let mut offset = 0;
+    for piece in msb0_ranges(range, bit_length, T::BIT_LENGTH).rev() {
+        set_bits(piece.clone(), bits.get_bits(offset..offset + piece.len()));
+        offset += piece.len();
+    }
+}
+
+/// Returns the LSB-0 index of the bit with the MSB-0 index `bit` in a bit array of `bit_length`
+/// bits, which counts the bits of each element from its most significant bit. If the bit array
+/// ends within its last element, that element is counted from its last bit in bounds.
+///
+/// ## Panics
+///
+/// This function will panic if the bit index is out of bounds of the bit array.
+#[track_caller]
+#[inline]
+fn msb0_index(bit: usize, bit_length: usize, element_length: usize) -> usize {
+    assert!(bit < bit_length);
+
+    let offset = bit % element_length;
+    let start = bit - offset;
+    let len = element_length.min(bit_length - start);
+    start + len - 1 - offset
+}
+
+/// Splits the MSB-0 range `range` of a bit array of `bit_length` bits into the LSB-0 ranges it
+/// covers in the individual elements, ordered from the first to the last element, see
+/// `msb0_index`.
+///
+/// ## Panics
+///
+/// This function will panic if the range is reversed or out of bounds of the bit array.
+#[track_caller]
+#[inline]
+fn msb0_ranges(
+    range: Range<usize>,
+    bit_length: usize,
+    element_length: usize,
+) -> impl DoubleEndedIterator<Item = Range<usize>> {
+    element_ranges(range, bit_length, element_length).map(move |chunk| {
+        let start = chunk.start - chunk.start % element_length;
+        let end = start + element_length.min(bit_length - start);
+        start + end - chunk.end..start + end - chunk.start
+    })
+}
+
+/// Rotates the bits of `array` in the range `range` towards the more significant bits by `n`
+/// bits.
+#[inline]
+fn rotate_range<T, A>(array: &mut A, range: Range<usize>, n: usize)
+where
+    T: BitField,
+    A: BitArrayMut<T> + ?Sized,
+{
+    if range.is_empty() {
+        return;
+    }
+
+    let mid = range.start + n % range.len();
+    reverse_range(array, range.clone());
+    reverse_range(array, range.start..mid);
+    reverse_range(array, mid..range.end);
+}
+
+/// Reverses the order of the bits of `array` in the range `range`.
+#[inline]
+fn reverse_range<T, A>(array: &mut A, range: Range<usize>)
+where
+    T: BitField,
+    A: BitArrayMut<T> + ?Sized,
+{
+    // reverses the `len` lower bits of `bits`
+    let reverse =
+        |bits: T, len: usize| T::from_raw_bits(bits.raw_bits().reverse_bits() >> (128 - len));
+
+    let (mut low, mut high) = (range.start, range.end);
+    loop {
+        let len = T::BIT_LENGTH.min((high - low) / 2);
+        if len == 0 {
+            break;
+        }
+
+        let low_bits = array.get_bits(low..low + len);
+        let high_bits = array.get_bits(high - len..high);
+        array.set_bits(low..low + len, reverse(high_bits, len));
+        array.set_bits(high - len..high, reverse(low_bits, len));
+        low += len;
+        high -= len;
+    }
+}
+
+/// Returns the index of the first bit with the value `value` in the range `range` of `array`.
+#[track_caller]
+#[inline]
+fn find_first<T, A>(array: &A, range: Range<usize>, value: bool) -> Option<usize>
+where
+    T: BitField,
+    A: BitArray<T> + ?Sized,
+{
+    for chunk in element_ranges(range, array.bit_length(), T::BIT_LENGTH) {
+        let len = chunk.len();
+        let bits = array.get_bits(chunk.clone());
+        let skipped = if value {
+            bits.trailing_zeros_in(..len)
+        } else {
+            bits.trailing_ones_in(..len)
+        };
+        if skipped < len {
+            return Some(chunk.start + skipped);
+        }
+    }
+    None
+}
+
+/// Returns the index of the last bit with the value `value` in the range `range` of `array`.
+#[track_caller]
+#[inline]
+fn find_last<T, A>(array: &A, range: Range<usize>, value: bool) -> Option<usize>
+where
+    T: BitField,
+    A: BitArray<T> + ?Sized,
+{
+    for chunk in element_ranges(range, array.bit_length(), T::BIT_LENGTH).rev() {
+        let len = chunk.len();
+        let bits = array.get_bits(chunk.clone());
+        let skipped = if value {
+            bits.leading_zeros_in(..len)
+        } else {
+            bits.leading_ones_in(..len)
+        };
+        if skipped < len {
+            return Some(chunk.end - 1 - skipped);
+        }
+    }
+    None
+}
+
+/// Returns the start of the first run of at least `len` bits with the value `value` in `array`
+/// which starts at a multiple of `align`.
+#[track_caller]
+#[inline]
+fn find_run<T, A>(array: &A, len: usize, align: usize, value: bool) -> Option<usize>
+where
+    T: BitField,
+    A: BitArray<T> + ?Sized,
+{
+    assert!(align != 0, "alignment must not be zero");
+
+    let align_up = |bit: usize| match bit % align {
+        0 => Some(bit),
+        rem => bit.checked_add(align - rem),
+    };
+
+    let bit_length = array.bit_length();
+    let mut start = 0;
+    loop {
+        // skip to the next candidate, then look for the last bit which interrupts the run
+        if len > 0 {
+            start = align_up(find_first(array, start..bit_length, value)?)?;
+        }
+        let end = start.checked_add(len).filter(|&end| end <= bit_length)?;
+        match find_last(array, start..end, !value) {
+            Some(bit) => start = bit + 1,
+            None => return Some(start),
+        }
+    }
+}
+
+/// Splits a bit array range into the parts which lie in the individual elements.
+///
+/// The returned iterator yields the parts in ascending order as bit array ranges, each of which
+/// is contained by a single element, and never yields empty ranges.
+///
+/// ## Panics
+///
+/// This function will panic if the range is reversed or out of bounds of the bit array.
+#[track_caller]
+#[inline]
+fn element_ranges(range: Range<usize>, bit_length: usize, element_length: usize) -> ElementRanges {
+    assert!(range.start <= range.end);
+    assert!(range.end <= bit_length);
+
+    ElementRanges {
+        range,
+        element_length,
+    }
+}
+
+/// Iterator returned by `element_ranges`.
+struct ElementRanges {
+    range: Range<usize>,
+    element_length: usize,
+}
+
+impl Iterator for ElementRanges {
+    type Item = Range<usize>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.range.start >= self.range.end {
+            return None;
+        }
+
+        let start = self.range.start;
+        let element_end = (start / self.element_length + 1) * self.element_length;
+        self.range.start = element_end.min(self.range.end);
+
+        Some(start..self.range.start)
+    }
+}
+
+impl DoubleEndedIterator for ElementRanges {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.range.start >= self.range.end {
+            return None;
+        }
+
+        let end = self.range.end;
+        let element_start = (end - 1) / self.element_length * self.element_length;
+        self.range.end = element_start.max(self.range.start);
+
+        Some(self.range.end..end)
+    }
 }
 
 #[inline]
 fn to_regular_range<T: RangeBounds<usize>>(generic_rage: &T, bit_length: usize) -> Range<usize> {
     let start = match generic_rage.start_bound() {
-        Bound::Excluded(&value) => value + 1,
+        Bound::Excluded(&value) => value.saturating_add(1),
         Bound::Included(&value) => value,
         Bound::Unbounded => 0,
     };
     let end = match generic_rage.end_bound() {
         Bound::Excluded(&value) => value,
-        Bound::Included(&value) => value + 1,
+        Bound::Included(&value) => value.saturating_add(1),
         Bound::Unbounded => bit_length,
     };
 
     start..end
 }
+
+/// Checks that `range` is in bounds of `bit_length` bits like `element_ranges`, which accepts
+/// empty ranges, and returns whether it contains any bits.
+#[track_caller]
+#[inline]
+fn check_counted_range(range: &Range<usize>, bit_length: usize) -> bool {
+    assert!(range.start <= range.end);
+    assert!(range.end <= bit_length);
+
+    range.start < range.end
+}
+
+#[inline]
+fn check_index(index: usize, bit_length: usize) -> Result<(), BitFieldError> {
+    if index >= bit_length {
+        return Err(BitFieldError::IndexOutOfBounds {
+            index,
+            length: bit_length,
+        });
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn check_range(range: &Range<usize>, bit_length: usize) -> Result<(), BitFieldError> {
+    if range.start >= range.end {
+        return Err(BitFieldError::EmptyRange {
+            start: range.start,
+            end: range.end,
+        });
+    }
+    if range.end > bit_length {
+        return Err(BitFieldError::IndexOutOfBounds {
+            index: range.end,
+            length: bit_length,
+        });
+    }
+
+    Ok(())
+}
+
+#[inline]
+fn check_range_width(range: &Range<usize>, max: usize) -> Result<(), BitFieldError> {
+    if range.len() > max {
+        return Err(BitFieldError::RangeTooWide {
+            length: range.len(),
+            max,
+        });
+    }
+
+    Ok(())
+}
@@ -1,5 +1,21 @@
+use core::convert::TryFrom;
+use core::iter;
+
+use bit_arr;
+use bitfield;
+use const_fn;
+use core::ops::RangeBounds;
+use BitArr;
 use BitArray;
+use BitArrayMut;
 use BitField;
+use BitFieldError;
+use BitSlice;
+use BitSliceMut;
+#[cfg(feature = "alloc")]
+use BitVec;
+use Field;
+use InvalidFieldValue;
 
 #[test]
 fn test_integer_bit_lengths() {
@@ -448,3 +464,2017 @@ fn test_get_bits_array() {
     test_array = [0x3f, 0x80, 0xaa];
     assert_eq!(test_array.get_bits(6..14), 0x00);
 }
+
+#[test]
+fn test_try_get_set_bit() {
+    let mut field = 0b1010u16;
+    assert_eq!(field.try_get_bit(1), Ok(true));
+    assert_eq!(field.try_get_bit(2), Ok(false));
+    assert_eq!(
+        field.try_get_bit(16),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 16,
+            length: 16
+        })
+    );
+
+    assert!(field.try_set_bit(15, true).is_ok());
+    assert_eq!(field, 0x800a);
+    assert_eq!(
+        field.try_set_bit(16, false).err(),
+        Some(BitFieldError::IndexOutOfBounds {
+            index: 16,
+            length: 16
+        })
+    );
+    assert_eq!(field, 0x800a);
+}
+
+#[test]
+fn test_try_get_set_bits() {
+    let mut field = 0xdeadu16;
+    assert_eq!(field.try_get_bits(..), Ok(0xdead));
+    assert_eq!(field.try_get_bits(4..=7), Ok(0xa));
+    assert_eq!(
+        field.try_get_bits(4..4),
+        Err(BitFieldError::EmptyRange { start: 4, end: 4 })
+    );
+    assert_eq!(
+        field.try_get_bits(12..=16),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 17,
+            length: 16
+        })
+    );
+    assert_eq!(
+        field.try_get_bits(0..=usize::MAX),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: usize::MAX,
+            length: 16
+        })
+    );
+
+    assert!(field.try_set_bits(12.., 0xb).is_ok());
+    assert_eq!(field, 0xbead);
+    assert_eq!(
+        field.try_set_bits(12.., 0x10).err(),
+        Some(BitFieldError::ValueOverflow)
+    );
+    let (start, end) = (8, 4);
+    assert_eq!(
+        field.try_set_bits(start..end, 0).err(),
+        Some(BitFieldError::EmptyRange { start: 8, end: 4 })
+    );
+    assert_eq!(field, 0xbead);
+}
+
+#[test]
+fn test_try_bits_array() {
+    let mut test_array = [0xffu8, 0x00u8, 0xffu8];
+    assert_eq!(test_array.try_get_bit(23), Ok(true));
+    assert_eq!(
+        test_array.try_get_bit(24),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 24,
+            length: 24
+        })
+    );
+    assert_eq!(test_array.try_get_bits(4..12), Ok(0x0f));
+    assert_eq!(
+        test_array.try_get_bits(4..13),
+        Err(BitFieldError::RangeTooWide { length: 9, max: 8 })
+    );
+    assert_eq!(
+        test_array.try_get_bits(20..25),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 25,
+            length: 24
+        })
+    );
+
+    assert_eq!(test_array.try_set_bit(8, true), Ok(()));
+    assert_eq!(test_array, [0xff, 0x01, 0xff]);
+    assert_eq!(test_array.try_set_bits(12..20, 0xaa), Ok(()));
+    assert_eq!(test_array, [0xff, 0xa1, 0xfa]);
+    assert_eq!(
+        test_array.try_set_bits(6..12, 0x40),
+        Err(BitFieldError::ValueOverflow)
+    );
+    assert_eq!(test_array, [0xff, 0xa1, 0xfa]);
+}
+
+/// A bit field which only implements the required methods of `BitField`, to test the default
+/// implementations of the others.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Flags(u16);
+
+impl BitField for Flags {
+    const BIT_LENGTH: usize = 16;
+
+    type Signed = i16;
+
+    fn get_bit(&self, bit: usize) -> bool {
+        self.0.get_bit(bit)
+    }
+
+    fn get_bits<T: RangeBounds<usize>>(&self, range: T) -> Self {
+        Flags(self.0.get_bits(range))
+    }
+
+    fn set_bit(&mut self, bit: usize, value: bool) -> &mut Self {
+        self.0.set_bit(bit, value);
+        self
+    }
+
+    fn set_bits<T: RangeBounds<usize>>(&mut self, range: T, value: Self) -> &mut Self {
+        self.0.set_bits(range, value.0);
+        self
+    }
+
+    fn get_bits_signed<T: RangeBounds<usize>>(&self, range: T) -> i16 {
+        self.0.get_bits_signed(range)
+    }
+
+    fn set_bits_signed<T: RangeBounds<usize>>(&mut self, range: T, value: i16) -> &mut Self {
+        self.0.set_bits_signed(range, value);
+        self
+    }
+
+    fn from_raw_bits(bits: u128) -> Self {
+        Flags(bits as u16)
+    }
+}
+
+#[test]
+fn test_default_methods() {
+    let mut flags = Flags(0xdead);
+    assert_eq!(flags.try_get_bit(15), Ok(true));
+    assert_eq!(
+        flags.try_get_bit(16),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 16,
+            length: 16
+        })
+    );
+    assert_eq!(flags.try_get_bits(4..8), Ok(Flags(0xa)));
+    assert_eq!(
+        flags.try_get_bits(4..4),
+        Err(BitFieldError::EmptyRange { start: 4, end: 4 })
+    );
+
+    assert!(flags.try_set_bit(0, false).is_ok());
+    assert_eq!(flags, Flags(0xdeac));
+    assert!(flags.try_set_bits(12..16, Flags(0xb)).is_ok());
+    assert_eq!(flags, Flags(0xbeac));
+    assert_eq!(
+        flags.try_set_bits(0..4, Flags(0x10)).err(),
+        Some(BitFieldError::ValueOverflow)
+    );
+    assert_eq!(
+        flags.try_set_bits(12..17, Flags(0)).err(),
+        Some(BitFieldError::IndexOutOfBounds {
+            index: 17,
+            length: 16
+        })
+    );
+    assert_eq!(flags, Flags(0xbeac));
+
+    assert_eq!(flags.with_bit(0, true), Flags(0xbead));
+    assert_eq!(flags.with_bits(4..12, Flags(0xef)), Flags(0xbefc));
+    assert_eq!(flags.with_cleared_bits(8..), Flags(0xac));
+    assert_eq!(flags.with_toggled_bit(15), Flags(0x3eac));
+
+    flags.toggle_bit(0).toggle_bits(8..16);
+    assert_eq!(flags, Flags(0x41ad));
+
+    assert_eq!(flags.count_ones_in(..), 7);
+    assert_eq!(flags.count_zeros_in(4..12), 5);
+    assert_eq!(flags.count_ones_in(3..3), 0);
+    assert_eq!(flags.leading_zeros_in(..), 1);
+    assert_eq!(flags.leading_ones_in(..15), 1);
+    assert_eq!(flags.trailing_zeros_in(1..), 1);
+    assert_eq!(flags.trailing_ones_in(..), 1);
+
+    assert!(flags.iter_ones().eq([0, 2, 3, 5, 7, 8, 14].iter().cloned()));
+    assert_eq!(flags.iter_zeros().next_back(), Some(15));
+    assert_eq!(flags.iter_bits().filter(|&bit| bit).count(), 7);
+    assert!(flags
+        .iter_one_runs()
+        .eq([0..1, 2..4, 5..6, 7..9, 14..15].iter().cloned()));
+    assert_eq!(flags.iter_zero_runs().next_back(), Some(15..16));
+
+    assert_eq!(flags.raw_bits(), 0x41ad);
+    assert_eq!(Flags::from_raw_bits(0x1_beef), Flags(0xbeef));
+    assert_eq!(
+        [0x1234u16, 0x5678].get_bits_as::<Flags, _>(8..24),
+        Flags(0x7812)
+    );
+
+    let mut flags = Flags(0x0ab0);
+    flags.rotate_bits_left(4..12, 4);
+    assert_eq!(flags, Flags(0x0ba0));
+    flags.rotate_bits_right(4..12, 12);
+    assert_eq!(flags, Flags(0x0ab0));
+    flags.reverse_bits_in(4..8);
+    assert_eq!(flags, Flags(0x0ad0));
+    flags.swap_bytes_in(..);
+    assert_eq!(flags, Flags(0xd00a));
+
+    assert_eq!(flags.extract_mask(Flags(0xf00f)), Flags(0xda));
+    flags.deposit_mask(Flags(0x0ff0), Flags(0x1234));
+    assert_eq!(flags, Flags(0xd34a));
+    assert_eq!(flags.get_split_bits(&[12..16, 0..4]), Flags(0xad));
+    flags.set_split_bits(&[12..16, 0..4], Flags(0x12));
+    assert_eq!(flags, Flags(0x2341));
+    assert_eq!(flags.get_field::<Flags, _>(4..12), Ok(Flags(0x34)));
+    flags.set_field(0..4, Flags(0x9));
+    assert_eq!(flags, Flags(0x2349));
+
+    assert!(flags.get_bit_msb0(2));
+    flags.set_bit_msb0(0, true);
+    assert_eq!(flags.get_bits_msb0(0..8), Flags(0xa3));
+    flags.set_bits_msb0(12..16, Flags(0x5));
+    assert_eq!(flags, Flags(0xa345));
+}
+
+macro_rules! signed_bits_tests {
+    ($($name:ident: $t:ty),*) => ($(
+        #[test]
+        fn $name() {
+            const BITS: usize = <$t>::BIT_LENGTH;
+
+            // fields are zero-extended instead of sign-extended
+            assert_eq!((-1 as $t).get_bits(0..3), 0b111);
+            assert_eq!((-1 as $t).get_bits(BITS - 3..), 0b111);
+            assert_eq!((-1 as $t).get_bits(..), -1);
+            assert_eq!(<$t>::MIN.get_bits(BITS - 1..), 1);
+            assert_eq!(<$t>::MIN.get_bits(..BITS - 1), 0);
+            assert_eq!(<$t>::MAX.get_bits(1..), <$t>::MAX >> 1);
+            assert!(<$t>::MIN.get_bit(BITS - 1));
+
+            // raw bit patterns
+            let mut field: $t = 0;
+            field.set_bits(0..3, 0b101);
+            assert_eq!(field, 0b101);
+            field.set_bits(BITS - 2.., 0b11);
+            assert_eq!(field, (0b11 << (BITS - 2)) | 0b101);
+            field.set_bits(BITS - 2.., 0b01);
+            assert_eq!(field, (0b01 << (BITS - 2)) | 0b101);
+
+            // negative values which fit into the range in two's complement
+            let mut field: $t = 0;
+            field.set_bits(2..6, -1);
+            assert_eq!(field, 0b111100);
+            field.set_bits(2..6, -8);
+            assert_eq!(field, 0b100000);
+            field.set_bits(2..6, -2);
+            assert_eq!(field.get_bits(2..6), 0b1110);
+            field.set_bits(.., -5);
+            assert_eq!(field, -5);
+            field.set_bits(BITS - 1.., -1);
+            assert_eq!(field, -5);
+            field.set_bits(BITS - 1.., 0);
+            assert_eq!(field, -5 & <$t>::MAX);
+
+            // values which don't fit
+            assert_eq!(field.try_set_bits(2..6, -9).err(), Some(BitFieldError::ValueOverflow));
+            assert_eq!(field.try_set_bits(2..6, 0x10).err(), Some(BitFieldError::ValueOverflow));
+            assert_eq!(field.try_set_bits(2..6, -1).ok().copied(), Some(field | 0b111100));
+
+            // bit arrays with fields spanning multiple elements
+            let mut array: [$t; 2] = [0, 0];
+            array.set_bits(BITS - 2..BITS + 2, -1);
+            assert_eq!(array, [0b11 << (BITS - 2), 0b11]);
+            assert_eq!(array.get_bits(BITS - 2..BITS + 2), 0b1111);
+            array.set_bits(BITS - 2..BITS + 2, -8);
+            assert_eq!(array, [0, 0b10]);
+            assert_eq!(array.get_bits(BITS - 2..BITS + 2), 0b1000);
+            assert_eq!(array.try_set_bits(BITS - 2..BITS + 2, -9), Err(BitFieldError::ValueOverflow));
+        }
+    )*)
+}
+
+signed_bits_tests! {
+    test_signed_bits_i8: i8,
+    test_signed_bits_i16: i16,
+    test_signed_bits_i32: i32,
+    test_signed_bits_i64: i64,
+    test_signed_bits_i128: i128,
+    test_signed_bits_isize: isize
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_signed_overflow() {
+    let mut field = 0i16;
+    field.set_bits(0..4, -9);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_unsigned_rejects_sign_extension() {
+    let mut field = 0u8;
+    field.set_bits(0..4, 0xff);
+}
+
+#[test]
+fn test_get_set_bits_signed() {
+    let mut field = 0u16;
+    field.set_bits_signed(0..12, -2048);
+    assert_eq!(field, 0x800);
+    assert_eq!(field.get_bits_signed(0..12), -2048);
+    field.set_bits_signed(0..12, 2047);
+    assert_eq!(field, 0x7ff);
+    assert_eq!(field.get_bits_signed(0..12), 2047);
+    field.set_bits_signed(12.., -1);
+    assert_eq!(field, 0xf7ff);
+    assert_eq!(field.get_bits_signed(12..), -1);
+    assert_eq!(field.get_bits_signed(..), -0x801);
+    assert_eq!(field.get_bits_signed(11..=11), 0);
+    assert_eq!(field.get_bits_signed(12..=12), -1);
+
+    let mut field = 0i64;
+    field.set_bits_signed(43..64, -0x10_0000);
+    assert_eq!(field, i64::MIN);
+    assert_eq!(field.get_bits_signed(43..), -0x10_0000);
+    field.set_bits_signed(0..21, 0xf_ffff);
+    assert_eq!(field.get_bits_signed(0..21), 0xf_ffff);
+    assert_eq!(field.get_bits(0..21), 0xf_ffff);
+
+    let mut field = 0u128;
+    field.set_bits_signed(100..110, -3);
+    assert_eq!(field, 0x3fd << 100);
+    assert_eq!(field.get_bits_signed(100..110), -3);
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_set_bits_signed_out_of_range() {
+    let mut field = 0u32;
+    field.set_bits_signed(0..12, 2048);
+}
+
+#[test]
+fn test_get_set_bits_signed_array() {
+    let mut test_array = [0u8; 3];
+    test_array.set_bits_signed(4..12, -2);
+    assert_eq!(test_array, [0xe0, 0x0f, 0x00]);
+    assert_eq!(test_array.get_bits_signed(4..12), -2);
+    test_array.set_bits_signed(10..16, 5);
+    assert_eq!(test_array, [0xe0, 0x17, 0x00]);
+    assert_eq!(test_array.get_bits_signed(10..16), 5);
+    assert_eq!(test_array.get_bits_signed(4..12), 0x7e);
+    test_array.set_bits_signed(14..20, -32);
+    assert_eq!(test_array, [0xe0, 0x17, 0x08]);
+    assert_eq!(test_array.get_bits_signed(14..20), -32);
+
+    let mut test_array = [0i32; 2];
+    test_array.set_bits_signed(20..41, -0x10_0000);
+    assert_eq!(test_array, [0, 0x100]);
+    assert_eq!(test_array.get_bits_signed(20..41), -0x10_0000);
+    assert_eq!(test_array.get_bits(20..41), 0x10_0000);
+}
+
+#[test]
+fn test_const_fn() {
+    const FIELD: u32 = const_fn::u32::with_bits(const_fn::u32::with_bit(0, 31, true), 4, 12, 0xab);
+    const HIGH: [bool; 2] = [
+        const_fn::u32::get_bit(FIELD, 31),
+        const_fn::u32::get_bit(FIELD, 30),
+    ];
+    const BITS: u32 = const_fn::u32::get_bits(FIELD, 4, 12);
+    const MASK: u32 = const_fn::u32::mask(4, 12);
+    static SIGNED: i8 = const_fn::i8::with_bits(0, 4, 8, -1);
+
+    assert_eq!(FIELD, 0x8000_0ab0);
+    assert_eq!(HIGH, [true, false]);
+    assert_eq!(BITS, 0xab);
+    assert_eq!(MASK, 0xff0);
+    assert_eq!(SIGNED, -16);
+    assert_eq!(const_fn::i8::get_bits(SIGNED, 4, 8), 0b1111);
+    assert_eq!(const_fn::i8::mask(0, 8), -1);
+    assert_eq!(const_fn::u128::mask(64, 128), 0xffff_ffff_ffff_ffff << 64);
+    assert_eq!(const_fn::u64::with_bit(u64::MAX, 63, false), u64::MAX >> 1);
+    assert_eq!(const_fn::usize::BIT_LENGTH, usize::BIT_LENGTH);
+
+    for start in 0..16 {
+        for end in start + 1..=16 {
+            let mut field = 0u16;
+            field.set_bits(start..end, 1);
+            assert_eq!(const_fn::u16::with_bits(0, start, end, 1), field);
+            assert_eq!(
+                const_fn::u16::get_bits(0xbeef, start, end),
+                0xbeef.get_bits(start..end)
+            );
+            assert_eq!(
+                const_fn::u16::mask(start, end),
+                (!0u16).get_bits(start..end) << start
+            );
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "value does not fit into bit range")]
+fn test_const_fn_with_bits_overflow() {
+    const_fn::u8::with_bits(0, 0, 4, 0x10);
+}
+
+#[test]
+fn test_with_bits() {
+    let field = 0u32
+        .with_bit(31, true)
+        .with_bits(0..8, 0xab)
+        .with_bits(8..=15, 0xcd)
+        .with_toggled_bit(16);
+    assert_eq!(field, 0x8001_cdab);
+    assert_eq!(field.with_bit(31, false), 0x0001_cdab);
+    assert_eq!(field.with_toggled_bit(31).with_toggled_bit(0), 0x0001_cdaa);
+    assert_eq!(field.with_cleared_bits(4..12), 0x8001_c00b);
+    assert_eq!(field.with_cleared_bits(16..), 0xcdab);
+    assert_eq!(field.with_cleared_bits(..), 0);
+    assert_eq!(field.with_bits(.., 0), 0);
+
+    let field = 0i16.with_bits(12.., -1).with_toggled_bit(0);
+    assert_eq!(field, -0x0fff);
+    assert_eq!(field.with_cleared_bits(15..), 0x7001);
+    assert_eq!(field.with_toggled_bit(15), 0x7001);
+}
+
+#[test]
+#[should_panic]
+fn test_with_toggled_bit_out_of_bounds() {
+    0u8.with_toggled_bit(8);
+}
+
+#[test]
+fn test_toggle_bits() {
+    let mut field = 0xf0f0u16;
+    field.toggle_bit(0).toggle_bit(15);
+    assert_eq!(field, 0x70f1);
+    field.toggle_bits(4..12);
+    assert_eq!(field, 0x7f01);
+    field.toggle_bits(..);
+    assert_eq!(field, 0x80fe);
+    field.toggle_bits(15..=15);
+    assert_eq!(field, 0x00fe);
+
+    let mut field = 0i8;
+    field.toggle_bits(4..);
+    assert_eq!(field, -16);
+    field.toggle_bit(7);
+    assert_eq!(field, 0x70);
+}
+
+#[test]
+fn test_toggle_bits_array() {
+    let mut test_array = [0x00u8, 0xff, 0x0f, 0xf0];
+    test_array.toggle_bit(0);
+    test_array.toggle_bit(31);
+    assert_eq!(test_array, [0x01, 0xff, 0x0f, 0x70]);
+    test_array.toggle_bits(4..28);
+    assert_eq!(test_array, [0xf1, 0x00, 0xf0, 0x7f]);
+    test_array.toggle_bits(8..16);
+    assert_eq!(test_array, [0xf1, 0xff, 0xf0, 0x7f]);
+    test_array.toggle_bits(3..5);
+    assert_eq!(test_array, [0xe9, 0xff, 0xf0, 0x7f]);
+    test_array.toggle_bits(12..12);
+    assert_eq!(test_array, [0xe9, 0xff, 0xf0, 0x7f]);
+    test_array.toggle_bits(..);
+    assert_eq!(test_array, [0x16, 0x00, 0x0f, 0x80]);
+
+    let mut test_array = [0u64; 3];
+    test_array.toggle_bits(63..129);
+    assert_eq!(test_array, [1 << 63, !0, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_toggle_bits_array_out_of_bounds() {
+    let mut test_array = [0u8; 2];
+    test_array.toggle_bits(4..17);
+}
+
+#[test]
+fn test_count_bits_in() {
+    let field = 0x0ff0_f00fu32;
+    assert_eq!(field.count_ones_in(..), 16);
+    assert_eq!(field.count_zeros_in(..), 16);
+    assert_eq!(field.count_ones_in(2..14), 4);
+    assert_eq!(field.count_zeros_in(2..14), 8);
+    assert_eq!(field.leading_zeros_in(..), 4);
+    assert_eq!(field.leading_zeros_in(..16), 0);
+    assert_eq!(field.leading_zeros_in(..12), 8);
+    assert_eq!(field.leading_zeros_in(4..12), 8);
+    assert_eq!(field.leading_ones_in(..28), 8);
+    assert_eq!(field.leading_ones_in(..16), 4);
+    assert_eq!(field.leading_ones_in(..), 0);
+    assert_eq!(field.trailing_zeros_in(..), 0);
+    assert_eq!(field.trailing_zeros_in(4..), 8);
+    assert_eq!(field.trailing_zeros_in(4..12), 8);
+    assert_eq!(field.trailing_ones_in(..), 4);
+    assert_eq!(field.trailing_ones_in(12..), 4);
+    assert_eq!(field.trailing_ones_in(20..28), 8);
+
+    let field = -1i64;
+    assert_eq!(field.count_ones_in(..), 64);
+    assert_eq!(field.leading_ones_in(..), 64);
+    assert_eq!(field.trailing_ones_in(3..), 61);
+    assert_eq!(field.leading_zeros_in(..), 0);
+    assert_eq!(0i64.leading_zeros_in(5..9), 4);
+    assert_eq!(0i64.trailing_zeros_in(60..), 4);
+}
+
+#[test]
+fn test_count_bits_in_array() {
+    let test_array = [0u64, !0, 0xff00, 0, 1 << 63];
+    assert_eq!(test_array.count_ones_in(..), 73);
+    assert_eq!(test_array.count_zeros_in(..), 247);
+    assert_eq!(test_array.count_ones_in(60..140), 68);
+    assert_eq!(test_array.count_ones_in(70..70), 0);
+    assert_eq!(test_array.leading_zeros_in(..), 0);
+    assert_eq!(test_array.leading_zeros_in(..319), 127 + 48);
+    assert_eq!(test_array.leading_zeros_in(..64), 64);
+    assert_eq!(test_array.leading_ones_in(..128), 64);
+    assert_eq!(test_array.leading_ones_in(100..128), 28);
+    assert_eq!(test_array.trailing_zeros_in(..), 64);
+    assert_eq!(test_array.trailing_zeros_in(128..), 8);
+    assert_eq!(test_array.trailing_zeros_in(136..136), 0);
+    assert_eq!(test_array.trailing_ones_in(64..), 64);
+    assert_eq!(test_array.trailing_ones_in(136..), 8);
+    assert_eq!(test_array.trailing_ones_in(319..), 1);
+
+    let mut test_array = [0u8; 40];
+    test_array[17] = 0x10;
+    assert_eq!(test_array.trailing_zeros_in(..), 140);
+    assert_eq!(test_array.leading_zeros_in(..), 320 - 141);
+    test_array.toggle_bits(..);
+    assert_eq!(test_array.trailing_ones_in(3..), 137);
+    assert_eq!(test_array.leading_ones_in(..300), 300 - 141);
+}
+
+#[test]
+fn test_count_bits_in_empty_range() {
+    for range in [0..0, 5..5, 32..32].iter().cloned() {
+        let field = 0x0ff0_f00fu32;
+        assert_eq!(field.count_ones_in(range.clone()), 0);
+        assert_eq!(field.count_zeros_in(range.clone()), 0);
+        assert_eq!(field.leading_zeros_in(range.clone()), 0);
+        assert_eq!(field.leading_ones_in(range.clone()), 0);
+        assert_eq!(field.trailing_zeros_in(range.clone()), 0);
+        assert_eq!(field.trailing_ones_in(range.clone()), 0);
+
+        let array = [0x0ff0_f00fu32, !0];
+        assert_eq!(array.count_ones_in(range.clone()), 0);
+        assert_eq!(array.count_zeros_in(range.clone()), 0);
+        assert_eq!(array.leading_zeros_in(range.clone()), 0);
+        assert_eq!(array.leading_ones_in(range.clone()), 0);
+        assert_eq!(array.trailing_zeros_in(range.clone()), 0);
+        assert_eq!(array.trailing_ones_in(range), 0);
+    }
+    assert_eq!(0u8.count_zeros_in(8..), 0);
+    assert_eq!([0u8; 2].count_zeros_in(16..), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_count_bits_in_empty_range_out_of_bounds() {
+    0u32.count_ones_in(33..33);
+}
+
+#[test]
+#[should_panic]
+fn test_count_bits_in_reversed_range() {
+    let start = 5;
+    0u32.trailing_ones_in(start..4);
+}
+
+#[test]
+fn test_find_bits_array() {
+    let mut bitmap = [0u64; 4];
+    assert_eq!(bitmap.first_one(), None);
+    assert_eq!(bitmap.last_one(), None);
+    assert_eq!(bitmap.first_zero(), Some(0));
+    assert_eq!(bitmap.last_zero(), Some(255));
+
+    bitmap.set_bit(70, true);
+    bitmap.set_bit(200, true);
+    assert_eq!(bitmap.first_one(), Some(70));
+    assert_eq!(bitmap.last_one(), Some(200));
+    assert_eq!(bitmap.first_one_in(71..), Some(200));
+    assert_eq!(bitmap.first_one_in(71..200), None);
+    assert_eq!(bitmap.first_one_in(70..70), None);
+    assert_eq!(bitmap.last_one_in(..200), Some(70));
+    assert_eq!(bitmap.last_one_in(71..200), None);
+    assert_eq!(bitmap.next_one_after(0), Some(70));
+    assert_eq!(bitmap.next_one_after(70), Some(200));
+    assert_eq!(bitmap.next_one_after(200), None);
+    assert_eq!(bitmap.next_one_after(255), None);
+    assert_eq!(bitmap.prev_one_before(255), Some(200));
+    assert_eq!(bitmap.prev_one_before(200), Some(70));
+    assert_eq!(bitmap.prev_one_before(70), None);
+    assert_eq!(bitmap.prev_one_before(0), None);
+
+    bitmap.toggle_bits(..);
+    assert_eq!(bitmap.first_zero(), Some(70));
+    assert_eq!(bitmap.last_zero(), Some(200));
+    assert_eq!(bitmap.first_zero_in(71..), Some(200));
+    assert_eq!(bitmap.first_zero_in(71..200), None);
+    assert_eq!(bitmap.last_zero_in(..200), Some(70));
+    assert_eq!(bitmap.last_zero_in(71..200), None);
+    assert_eq!(bitmap.next_zero_after(70), Some(200));
+    assert_eq!(bitmap.next_zero_after(200), None);
+    assert_eq!(bitmap.prev_zero_before(200), Some(70));
+    assert_eq!(bitmap.prev_zero_before(70), None);
+    assert_eq!(bitmap.first_one(), Some(0));
+    assert_eq!(bitmap.last_one(), Some(255));
+
+    let test_array = [0x80u8, 0x01];
+    for bit in 0..16 {
+        let expected = (bit + 1..16).find(|&i| test_array.get_bit(i));
+        assert_eq!(test_array.next_one_after(bit), expected);
+        let expected = (0..bit).rev().find(|&i| !test_array.get_bit(i));
+        assert_eq!(test_array.prev_zero_before(bit), expected);
+    }
+}
+
+#[test]
+#[should_panic]
+fn test_next_one_after_out_of_bounds() {
+    [0u8; 2].next_one_after(16);
+}
+
+#[test]
+fn test_find_runs_array() {
+    let mut bitmap = [0u64; 4];
+    assert_eq!(bitmap.find_zero_run(0, 1), Some(0));
+    assert_eq!(bitmap.find_zero_run(256, 1), Some(0));
+    assert_eq!(bitmap.find_zero_run(257, 1), None);
+    assert_eq!(bitmap.find_one_run(1, 1), None);
+    assert_eq!(bitmap.find_one_run(0, 1), Some(0));
+
+    bitmap.set_range(0..3);
+    bitmap.set_range(100..101);
+    assert_eq!(bitmap, [0b111, 1 << 36, 0, 0]);
+    assert_eq!(bitmap.find_zero_run(1, 1), Some(3));
+    assert_eq!(bitmap.find_zero_run(97, 1), Some(3));
+    assert_eq!(bitmap.find_zero_run(98, 1), Some(101));
+    assert_eq!(bitmap.find_zero_run(64, 64), Some(128));
+    assert_eq!(bitmap.find_zero_run(64, 32), Some(32));
+    assert_eq!(bitmap.find_zero_run(32, 32), Some(32));
+    assert_eq!(bitmap.find_zero_run(96, 32), Some(128));
+    assert_eq!(bitmap.find_zero_run(155, 1), Some(101));
+    assert_eq!(bitmap.find_zero_run(156, 1), None);
+    assert_eq!(bitmap.find_zero_run(4, 3), Some(3));
+    assert_eq!(bitmap.find_zero_run(4, 5), Some(5));
+    assert_eq!(bitmap.find_zero_run(1, usize::MAX), None);
+
+    let start = bitmap.find_zero_run(70, 8).unwrap();
+    assert_eq!(start, 8);
+    bitmap.set_range(start..start + 70);
+    assert_eq!(bitmap.find_one_run(70, 1), Some(8));
+    assert_eq!(bitmap.find_one_run(71, 1), None);
+    assert_eq!(bitmap.find_one_run(3, 2), Some(0));
+    assert_eq!(bitmap.find_one_run(4, 4), Some(8));
+    assert_eq!(bitmap.find_one_run(1, 50), Some(0));
+    assert_eq!(bitmap.find_one_run(4, 50), Some(50));
+    assert_eq!(bitmap.count_ones_in(..), 74);
+    bitmap.clear_range(start..start + 70);
+    assert_eq!(bitmap, [0b111, 1 << 36, 0, 0]);
+
+    bitmap.set_range(..);
+    assert_eq!(bitmap, [!0; 4]);
+    bitmap.clear_range(1..255);
+    assert_eq!(bitmap, [1, 0, 0, 1 << 63]);
+    bitmap.clear_range(10..10);
+    bitmap.set_range(10..10);
+    assert_eq!(bitmap, [1, 0, 0, 1 << 63]);
+
+    let mut signed = [0i8, 0x10, -128];
+    signed.set_range(4..20);
+    assert_eq!(signed, [-16, -1, -128 | 0x0f]);
+}
+
+#[test]
+#[should_panic(expected = "alignment must not be zero")]
+fn test_find_zero_run_zero_alignment() {
+    [0u8; 2].find_zero_run(1, 0);
+}
+
+#[test]
+fn test_iter_bits() {
+    let field = 0x8000_0000_0000_0101u64;
+    let mut ones = field.iter_ones();
+    assert_eq!(ones.len(), 3);
+    assert_eq!(ones.next(), Some(0));
+    assert_eq!(ones.next_back(), Some(63));
+    assert_eq!(ones.len(), 1);
+    assert_eq!(ones.next(), Some(8));
+    assert_eq!(ones.next(), None);
+    assert_eq!(ones.next_back(), None);
+    assert_eq!(ones.len(), 0);
+
+    let mut zeros = (-2i8).iter_zeros();
+    assert_eq!(zeros.len(), 1);
+    assert_eq!(zeros.next_back(), Some(0));
+    assert_eq!(zeros.next(), None);
+    assert!(0u16.iter_zeros().eq(0..16));
+    assert!(0u16.iter_zeros().rev().eq((0..16).rev()));
+    assert_eq!(0u16.iter_ones().next(), None);
+
+    let mut bits = 0b1010u8.iter_bits();
+    assert_eq!(bits.len(), 8);
+    assert!(bits
+        .clone()
+        .eq([false, true, false, true, false, false, false, false]
+            .iter()
+            .cloned()));
+    assert_eq!(bits.next_back(), Some(false));
+}
+
+#[test]
+fn test_iter_bits_array() {
+    let bitmap = [0u64, 1 << 63, 0, 0, 0x11];
+    assert!(bitmap.iter_ones().eq([127, 256, 260].iter().cloned()));
+    assert!(bitmap.iter_ones().rev().eq([260, 256, 127].iter().cloned()));
+    let mut ones = bitmap.iter_ones();
+    assert_eq!(ones.next(), Some(127));
+    assert_eq!(ones.next_back(), Some(260));
+    assert_eq!(ones.next_back(), Some(256));
+    assert_eq!(ones.next(), None);
+    assert_eq!(ones.next_back(), None);
+
+    assert_eq!(bitmap.iter_zeros().count(), 320 - 3);
+    assert_eq!(bitmap.iter_zeros().nth(127), Some(128));
+    assert_eq!(bitmap.iter_zeros().next_back(), Some(319));
+
+    let bits = bitmap.iter_bits();
+    assert_eq!(bits.len(), 320);
+    assert!(bits
+        .enumerate()
+        .filter(|&(_, bit)| bit)
+        .map(|(i, _)| i)
+        .eq(bitmap.iter_ones()));
+
+    let empty: [u8; 0] = [];
+    assert_eq!(empty.iter_ones().next(), None);
+    assert_eq!(empty.iter_zeros().next_back(), None);
+    assert_eq!(empty.iter_bits().len(), 0);
+}
+
+#[test]
+fn test_iter_runs() {
+    let field = 0xf00f_0ff0u32;
+    assert!(field
+        .iter_one_runs()
+        .eq([4..12, 16..20, 28..32].iter().cloned()));
+    assert!(field
+        .iter_zero_runs()
+        .eq([0..4, 12..16, 20..28].iter().cloned()));
+    assert!(field
+        .iter_one_runs()
+        .rev()
+        .eq([28..32, 16..20, 4..12].iter().cloned()));
+    let mut runs = field.iter_zero_runs();
+    assert_eq!(runs.next_back(), Some(20..28));
+    assert_eq!(runs.next(), Some(0..4));
+    assert_eq!(runs.next_back(), Some(12..16));
+    assert_eq!(runs.next(), None);
+    assert_eq!(runs.next_back(), None);
+
+    assert!((-1i8).iter_one_runs().eq(iter::once(0..8)));
+    assert_eq!((-1i8).iter_zero_runs().next(), None);
+    assert!(0u128.iter_zero_runs().eq(iter::once(0..128)));
+}
+
+#[test]
+fn test_iter_runs_array() {
+    let bitmap = [!0u64, !0, 0x0f, 0, 0, 1 << 63];
+    assert!(bitmap
+        .iter_one_runs()
+        .eq([0..132, 383..384].iter().cloned()));
+    assert!(bitmap.iter_zero_runs().eq(iter::once(132..383)));
+    assert!(bitmap
+        .iter_one_runs()
+        .rev()
+        .eq([383..384, 0..132].iter().cloned()));
+    assert!(bitmap.iter_zero_runs().rev().eq(iter::once(132..383)));
+
+    let bitmap = [0b1010_1010u8; 2];
+    assert_eq!(bitmap.iter_one_runs().count(), 8);
+    assert!(bitmap
+        .iter_zero_runs()
+        .step_by(3)
+        .eq([0..1, 6..7, 12..13].iter().cloned()));
+    assert!(bitmap
+        .iter_one_runs()
+        .map(|run| run.start)
+        .eq(bitmap.iter_ones()));
+
+    let empty: [u32; 0] = [];
+    assert_eq!(empty.iter_one_runs().next(), None);
+    assert_eq!(empty.iter_zero_runs().next_back(), None);
+}
+
+#[test]
+fn test_raw_bits() {
+    assert_eq!(0x12u8.raw_bits(), 0x12);
+    assert_eq!((-2i8).raw_bits(), 0xfe);
+    assert_eq!(u128::MAX.raw_bits(), u128::MAX);
+    assert_eq!(i128::MIN.raw_bits(), 1 << 127);
+
+    assert_eq!(u16::from_raw_bits(0x1_2345), 0x2345);
+    assert_eq!(i8::from_raw_bits(0x80), -128);
+    assert_eq!(i64::from_raw_bits(u128::MAX), -1);
+    assert_eq!(usize::from_raw_bits(0), 0);
+}
+
+#[test]
+fn test_get_bits_as() {
+    let header: [u8; 8] = [0x45, 0x00, 0x00, 0x54, 0xde, 0xad, 0xbe, 0xef];
+    assert_eq!(header.get_bits_as::<u32, _>(..32), 0x5400_0045);
+    assert_eq!(header.get_bits_as::<u64, _>(..), 0xefbe_adde_5400_0045);
+    assert_eq!(header.get_bits_as::<u128, _>(4..60), 0x00fb_eadd_e540_0004);
+    assert_eq!(header.get_bits_as::<u16, _>(28..40), 0xde5);
+    assert_eq!(header.get_bits_as::<u8, _>(0..4), 5);
+    assert_eq!(header.get_bits_as::<i32, _>(32..64), 0xefbe_addeu32 as i32);
+    assert_eq!(header.get_bits_as::<i8, _>(60..64), 0xe);
+
+    let words = [0x1234_5678u32, 0x9abc_def0, 0x0fed_cba9];
+    assert_eq!(words.get_bits_as::<u8, _>(28..36), 0x01);
+    assert_eq!(
+        words.get_bits_as::<u128, _>(..96),
+        0x0fed_cba9_9abc_def0_1234_5678
+    );
+    assert_eq!(words.get_bits_as::<u64, _>(16..80), 0xcba9_9abc_def0_1234);
+}
+
+#[test]
+fn test_set_bits_from() {
+    let mut buffer = [0u8; 8];
+    buffer.set_bits_from(4..60, 0x00fb_eadd_e540_0004u64);
+    assert_eq!(buffer, [0x40, 0x00, 0x00, 0x54, 0xde, 0xad, 0xbe, 0x0f]);
+
+    buffer.set_bits_from(.., 0u128);
+    assert_eq!(buffer, [0; 8]);
+
+    buffer.set_bits_from(12..28, -2i32);
+    assert_eq!(buffer, [0x00, 0xe0, 0xff, 0x0f, 0, 0, 0, 0]);
+
+    buffer.set_bits_from(63..64, 1u16);
+    assert_eq!(buffer.get_bits_as::<u64, _>(..), 0x8000_0000_0fff_e000);
+
+    let mut words = [0u16; 3];
+    words.set_bits_from(8..40, 0x1234_5678u32);
+    assert_eq!(words, [0x7800, 0x3456, 0x0012]);
+    assert_eq!(words.get_bits_as::<u32, _>(8..40), 0x1234_5678);
+}
+
+#[test]
+#[should_panic]
+fn test_get_bits_as_too_wide() {
+    [0u8; 4].get_bits_as::<u16, _>(0..17);
+}
+
+#[test]
+#[should_panic]
+fn test_set_bits_from_too_large() {
+    let mut buffer = [0u8; 4];
+    buffer.set_bits_from(0..12, 0x1000u16);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_bit_vec_push_pop() {
+    let mut bits: BitVec<u8> = BitVec::new();
+    for i in 0..20 {
+        bits.push(i % 3 == 0);
+    }
+    assert_eq!(bits.len(), 20);
+    assert_eq!(bits.bit_length(), 20);
+    assert_eq!(bits.as_slice(), &[0b0100_1001, 0b1001_0010, 0b0100]);
+    assert!(bits
+        .iter_ones()
+        .eq([0, 3, 6, 9, 12, 15, 18].iter().cloned()));
+    assert_eq!(bits.count_zeros_in(..), 13);
+    assert_eq!(bits.last_zero(), Some(19));
+
+    assert_eq!(bits.pop(), Some(false));
+    assert_eq!(bits.pop(), Some(true));
+    assert_eq!(bits.as_slice(), &[0b0100_1001, 0b1001_0010, 0]);
+    assert_eq!(bits.pop(), Some(false));
+    assert_eq!(bits.pop(), Some(false));
+    assert_eq!(bits.as_slice(), &[0b0100_1001, 0b1001_0010]);
+
+    bits.clear();
+    assert_eq!(bits.pop(), None);
+    assert_eq!(bits, BitVec::new());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_bit_vec_resize() {
+    let mut bits: BitVec<u32> = BitVec::repeat(true, 100);
+    assert_eq!(bits.as_slice(), &[!0, !0, !0, 0xf]);
+    assert_eq!(bits.count_zeros_in(..), 0);
+    assert_eq!(bits.first_zero(), None);
+    assert_eq!(bits.iter_zero_runs().next(), None);
+    assert_eq!(bits.find_zero_run(1, 1), None);
+
+    bits.truncate(33);
+    assert_eq!(bits.as_slice(), &[!0, 1]);
+    bits.truncate(40);
+    assert_eq!(bits.len(), 33);
+
+    bits.resize(70, false);
+    assert_eq!(bits.as_slice(), &[!0, 1, 0]);
+    assert_eq!(bits.find_zero_run(37, 1), Some(33));
+    assert_eq!(bits.find_zero_run(38, 1), None);
+    bits.resize(66, true);
+    assert_eq!(bits.as_slice(), &[!0, 1, 0]);
+
+    let from_iter: BitVec<u32> = (0..66).map(|bit| bit <= 32).collect();
+    assert_eq!(bits, from_iter);
+
+    bits.truncate(0);
+    assert!(bits.is_empty());
+    assert!(bits.as_slice().is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_bit_vec_bit_array() {
+    let mut bits: BitVec<u8> = BitVec::new();
+    bits.extend_from_bits(&[0x78, 0x56, 0x34, 0x12], 4..28);
+    bits.extend_from_bits(&[0xffu8, 0x01], 7..9);
+    assert_eq!(bits.len(), 26);
+    assert_eq!(bits.as_slice(), &[0x67, 0x45, 0x23, 0b11]);
+
+    let mut twice = bits.clone();
+    twice.extend_from_bitslice(bits.bit_slice(..));
+    twice.extend_from_bitslice(bits.bit_slice(3..3));
+    assert_eq!(twice.len(), 52);
+    assert_eq!(twice.get_bits_as::<u32, _>(26..), 0x0323_4567);
+
+    assert_eq!(bits.get_bits_as::<u32, _>(..), 0x0323_4567);
+    bits.set_bits(20..26, 0b10_1010);
+    assert_eq!(bits.get_bits(18..26), 0b1010_1000);
+    bits.toggle_bits(..);
+    assert_eq!(bits.as_slice(), &[0x98, 0xba, 0x5c, 0b01]);
+    bits.set_range(4..);
+    assert_eq!(bits.as_slice(), &[0xf8, 0xff, 0xff, 0b11]);
+    assert_eq!(bits.leading_ones_in(..), 23);
+    bits.clear_range(8..24);
+    assert!(bits.iter_one_runs().eq([3..8, 24..26].iter().cloned()));
+    assert_eq!(bits.iter_bits().len(), 26);
+
+    assert_eq!(
+        bits.try_get_bit(26),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 26,
+            length: 26
+        })
+    );
+    assert_eq!(
+        bits.try_set_bits(20..27, 0),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 27,
+            length: 26
+        })
+    );
+    assert_eq!(bits.try_set_bits(24..26, 0), Ok(()));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic]
+fn test_bit_vec_out_of_bounds() {
+    let bits: BitVec<u8> = BitVec::repeat(false, 10);
+    bits.get_bit(10);
+}
+
+#[test]
+fn test_bit_arr() {
+    type Mask = bit_arr!(70, u32);
+    static ONES: Mask = Mask::ONES;
+
+    assert_eq!(ONES.as_slice(), &[!0, !0, 0b11_1111]);
+    assert_eq!(ONES.bit_length(), 70);
+    assert_eq!(ONES.count_ones_in(..), 70);
+    assert_eq!(ONES.last_one(), Some(69));
+    assert_eq!(ONES.iter_zeros().next(), None);
+    assert_eq!(Mask::ZERO, Mask::default());
+    assert_eq!(Mask::ZERO.iter_zero_runs().next(), Some(0..70));
+    assert_eq!(Mask::ZERO.find_zero_run(70, 1), Some(0));
+    assert_eq!(Mask::ZERO.find_zero_run(71, 1), None);
+
+    let mut mask = Mask::new();
+    mask.set_range(30..70);
+    assert_eq!(mask.into_inner(), [0xc000_0000, !0, 0b11_1111]);
+    mask.toggle_bits(..);
+    assert_eq!(mask.as_slice(), &[0x3fff_ffff, 0, 0]);
+    mask.set_bits_from(60..70, 0x3ffu16);
+    assert_eq!(mask.get_bits_as::<u16, _>(60..70), 0x3ff);
+    assert_eq!(mask.count_ones_in(..), 40);
+    assert_eq!(
+        mask.try_set_bit(70, true),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 70,
+            length: 70
+        })
+    );
+
+    let signed = BitArr::<9, 2, i8>::ONES;
+    assert_eq!(signed.into_inner(), [-1, 1]);
+    assert_eq!(signed.get_bits_signed(1..9), -1);
+
+    let empty = BitArr::<0, 0, u64>::ONES;
+    assert!(empty.is_empty());
+    assert_eq!(empty.first_one(), None);
+
+    let bits: BitArr<100, 2, u64> = <bit_arr!(100, u64)>::new();
+    assert_eq!(bits.bit_length(), 100);
+    assert_eq!(<bit_arr!(200)>::ONES.count_ones_in(..), 200);
+    assert_eq!(<bit_arr!(4 * 8 + 1, u8)>::ZERO.as_slice(), &[0; 5]);
+}
+
+#[test]
+#[should_panic]
+fn test_bit_arr_out_of_bounds() {
+    let mut mask = BitArr::<10, 2, u8>::ZERO;
+    mask.set_bit(10, true);
+}
+
+#[test]
+fn test_set_algebra() {
+    let a = [0b1100u8, 0xf0, 0x0f];
+    let b = [0b1010u8, 0xff];
+    let none: &[u8] = &[];
+
+    let mut set = a;
+    set.union_with(&b[..]);
+    assert_eq!(set, [0b1110, 0xff, 0x0f]);
+    set = a;
+    set.intersect_with(&b[..]);
+    assert_eq!(set, [0b1000, 0xf0, 0]);
+    set = a;
+    set.difference_with(&b[..]);
+    assert_eq!(set, [0b0100, 0, 0x0f]);
+    set = a;
+    set.symmetric_difference_with(&b[..]);
+    assert_eq!(set, [0b0110, 0x0f, 0x0f]);
+    set.complement();
+    assert_eq!(set, [0b1111_1001, 0xf0, 0xf0]);
+
+    let mut short = b;
+    short.union_with(&a[..]);
+    assert_eq!(short, [0b1110, 0xff]);
+    short.intersect_with(none);
+    assert!(short.is_clear());
+
+    assert!([0b1000u8, 0xf0].is_subset(&a[..]));
+    assert!(!a.is_subset(&b[..]));
+    assert!(a.is_superset(&[0b1000, 0xf0][..]));
+    assert!(a.is_superset(none));
+    assert!(!b.is_superset(&a[..]));
+    assert!([0b0011i8].is_disjoint(&[0b1100, -1][..]));
+    assert!(!a.is_disjoint(&b[..]));
+    assert!(a.intersects(&b[..]));
+    assert!(!a.intersects(none));
+
+    let empty: [u32; 0] = [];
+    assert!(empty.is_clear());
+    assert!(empty.is_full());
+    assert!([-1i16, -1].is_full());
+    assert!(![-1i16, 0x7fff].is_full());
+}
+
+#[test]
+fn test_set_algebra_bit_arr() {
+    let mut set = BitArr::<12, 2, u8>::ZERO;
+    set.union_with(&[0x0f, 0xff][..]);
+    assert_eq!(set.as_slice(), &[0x0f, 0x0f]);
+    assert!(!set.is_full());
+    set.symmetric_difference_with(&[0xff, 0xff][..]);
+    assert_eq!(set.as_slice(), &[0xf0, 0x00]);
+    set.complement();
+    assert_eq!(set.as_slice(), &[0x0f, 0x0f]);
+    assert!(set.is_superset(&[0x01, 0x08][..]));
+    assert!(!set.is_superset(&[0x01, 0x10][..]));
+    set.union_with(&[0xf0][..]);
+    assert!(set.is_full());
+    assert!(BitArr::<12, 2, u8>::ONES.is_full());
+
+    // operands of other bit array types and bit lengths which aren't multiples of the element
+    let mut other = BitArr::<10, 2, u8>::ONES;
+    other.clear_range(4..6);
+    assert!(!set.is_subset(&other));
+    assert!(set.is_superset(&other));
+    set.intersect_with(&other);
+    assert_eq!(set.as_slice(), &[0xcf, 0x03]);
+    assert!(set.is_subset(&other));
+    set.difference_with(&other);
+    assert!(set.is_clear());
+    set.union_with(&other);
+    set.symmetric_difference_with(&BitArr::<12, 2, u8>::ONES);
+    assert_eq!(set.as_slice(), &[0x30, 0x0c]);
+    assert!(set.is_disjoint(&other));
+    assert!([0xffu8, 0xff].intersects(&set));
+}
+
+#[test]
+fn test_bit_slice() {
+    let bitmap = [0x1234_5678u32, 0x9abc_def0, 0x0fed_cba9];
+    let view = bitmap.bit_slice(13..83);
+    assert_eq!(view.len(), 70);
+    assert_eq!(
+        view.get_bits_as::<u128, _>(..),
+        bitmap.get_bits_as::<u128, _>(13..83)
+    );
+    assert_eq!(view.get_bits(0..32), bitmap.get_bits(13..45));
+    assert_eq!(view.get_bit(0), bitmap.get_bit(13));
+    assert_eq!(view.count_ones_in(..), bitmap.count_ones_in(13..83));
+    assert_eq!(view.first_one(), Some(1));
+    assert_eq!(view.first_zero(), Some(0));
+    assert_eq!(view.last_one(), Some(69));
+    assert_eq!(view.last_zero(), Some(68));
+    assert!(view
+        .iter_ones()
+        .map(|bit| bit + 13)
+        .eq(bitmap.iter_ones().filter(|bit| (13..83).contains(bit))));
+    assert!(view
+        .iter_zero_runs()
+        .rev()
+        .map(|run| run.start + 13..run.end + 13)
+        .eq(bitmap
+            .bit_slice(..83)
+            .iter_zero_runs()
+            .rev()
+            .take_while(|run| run.end > 13)
+            .map(|run| run.start.max(13)..run.end)));
+
+    let (low, high) = view.split_at(19);
+    assert_eq!(low.len(), 19);
+    assert_eq!(high.len(), 51);
+    assert_eq!(high.get_bits(0..8), bitmap.get_bits(32..40));
+    assert_eq!(high.slice(8..16).get_bits(..), bitmap.get_bits(40..48));
+    assert!(low.iter_bits().chain(high.iter_bits()).eq(view.iter_bits()));
+
+    let empty = BitSlice::new(&bitmap, 96..);
+    assert!(empty.is_empty());
+    assert_eq!(empty.first_one(), None);
+}
+
+#[test]
+fn test_bit_slice_read_api() {
+    fn ones_in_first_word<A: BitArray<u64> + ?Sized>(bits: &A) -> usize {
+        bits.count_ones_in(..bits.bit_length().min(64))
+    }
+
+    let bitmap = [0x0ff0_0000_f00fu64, 0xdead_beef_0000_0001];
+    let mut copy = bitmap;
+    let view = bitmap.bit_slice(5..115);
+    let copied = view;
+    let view_mut = copy.bit_slice_mut(5..115);
+
+    // a shared view can be used wherever a read-only bit array is expected
+    assert_eq!(ones_in_first_word(&view), 13);
+    assert_eq!(ones_in_first_word(&view_mut), 13);
+    assert_eq!(ones_in_first_word(&bitmap[..]), 16);
+
+    assert_eq!(copied.bit_length(), view_mut.bit_length());
+    assert_eq!(view.get_bits(3..40), view_mut.get_bits(3..40));
+    assert_eq!(view.try_get_bit(110), view_mut.try_get_bit(110));
+    assert_eq!(view.try_get_bits(100..111), view_mut.try_get_bits(100..111));
+    assert_eq!(view.leading_zeros_in(..70), view_mut.leading_zeros_in(..70));
+    assert_eq!(view.trailing_ones_in(..), view_mut.trailing_ones_in(..));
+    assert_eq!(view.last_zero_in(90..), view_mut.last_zero_in(90..));
+    assert_eq!(view.next_one_after(30), view_mut.next_one_after(30));
+    assert_eq!(view.prev_zero_before(4), view_mut.prev_zero_before(4));
+    assert_eq!(view.find_zero_run(12, 8), view_mut.find_zero_run(12, 8));
+    assert_eq!(view.find_one_run(4, 1), view_mut.find_one_run(4, 1));
+    assert!(view.is_subset(&view_mut));
+    assert!(view.is_superset(&copied));
+    assert!(view.intersects(&[0x80u64][..]));
+    assert!(!view.is_clear());
+    assert!(!view.is_full());
+    assert_eq!(
+        view.bit_slice(8..16).get_bits(..),
+        view_mut.bit_slice(8..16).get_bits(..)
+    );
+    assert!(BitSlice::new(&[0u8], 8..).is_clear());
+    assert!(BitSlice::new(&[0u8], 8..).is_full());
+    assert_eq!(
+        view.try_get_bit(110),
+        Err(BitFieldError::IndexOutOfBounds {
+            index: 110,
+            length: 110
+        })
+    );
+
+    // a shared view is an operand of the set operations
+    let mut other = [0u64; 2];
+    other.union_with(&view);
+    assert_eq!(
+        other[..].get_bits_as::<u128, _>(..110),
+        view.get_bits_as(..)
+    );
+    other.difference_with(&view);
+    assert!(other.is_clear());
+    BitSliceMut::new(&mut other, 10..).union_with(&view.slice(10..));
+    assert_eq!(other[..].get_bits(..10), 0);
+    assert_eq!(
+        other[..].get_bits_as::<u128, _>(10..110),
+        view.get_bits_as(10..)
+    );
+}
+
+#[test]
+fn test_bit_slice_mut() {
+    let mut bitmap = [0u8; 4];
+    {
+        let mut view = bitmap.bit_slice_mut(3..27);
+        assert_eq!(view.bit_length(), 24);
+        view.set_bits(0..8, 0xff);
+        view.set_bits_from(8..24, 0xa5a5u16);
+        assert_eq!(view.get_bits_as::<u32, _>(..), 0xa5_a5ff);
+        assert_eq!(view.first_zero(), Some(9));
+        assert_eq!(view.find_one_run(3, 4), Some(0));
+        assert_eq!(view.find_zero_run(2, 1), Some(11));
+        assert!(view.iter_ones().take(9).eq(0..9));
+        view.complement();
+        assert_eq!(view.get_bits_as::<u32, _>(..), 0x5a_5a00);
+        view.union_with(&[0x0f][..]);
+        assert_eq!(view.get_bits(0..8), 0x0f);
+        assert!(view.is_superset(&[0x0f][..]));
+        assert!(!view.is_superset(&[0x0f, 0, 0, 1][..]));
+        view.intersect_with(&[0xff][..]);
+        assert!(view.as_bit_slice().iter_ones().eq(0..4));
+    }
+    assert_eq!(bitmap, [0x78, 0, 0, 0]);
+
+    let mut bitmap = [0u8; 4];
+    {
+        let view = bitmap.bit_slice_mut(4..28);
+        let (mut low, mut high) = view.split_at_element_mut(4);
+        low.set_range(..);
+        high.toggle_bits(..);
+        assert_eq!(low.len(), 4);
+        assert_eq!(high.len(), 20);
+        assert_eq!(high.slice_mut(16..).get_bits(..), 0xf);
+    }
+    assert_eq!(bitmap, [0xf0, 0xff, 0xff, 0x0f]);
+
+    let (low, high) = bitmap.bit_slice_mut(4..28).split_at_element_mut(0);
+    assert!(low.is_empty());
+    assert_eq!(high.len(), 24);
+    let (low, high) = high.split_at_element_mut(24);
+    assert_eq!(low.count_ones_in(..), 24);
+    assert!(high.is_empty());
+}
+
+#[test]
+#[should_panic]
+fn test_bit_slice_out_of_bounds() {
+    let bitmap = [0u8; 4];
+    bitmap.bit_slice(4..12).get_bits(4..9);
+}
+
+#[test]
+#[should_panic(expected = "split point must be at an element boundary")]
+fn test_bit_slice_mut_unaligned_split() {
+    let mut bitmap = [0u8; 4];
+    bitmap.bit_slice_mut(4..28).split_at_element_mut(5);
+}
+
+#[test]
+fn test_copy_bits_from() {
+    let src = [0x1234_5678u32, 0x9abc_def0, 0x0fed_cba9];
+
+    let mut dst = [0u32; 4];
+    dst.copy_bits_from(3, &src[..], 5..90);
+    for bit in 0..dst.bit_length() {
+        let expected = (3..88).contains(&bit) && src.get_bit(bit + 2);
+        assert_eq!(dst.get_bit(bit), expected, "bit {}", bit);
+    }
+
+    let mut dst = [!0u32; 3];
+    dst.copy_bits_from(40, &src[..], 0..0);
+    assert_eq!(dst, [!0; 3]);
+    dst.copy_bits_from(0, &src[..], ..);
+    assert_eq!(dst, src);
+    dst.bit_slice_mut(4..60).copy_bits_from(0, &src[..], 64..96);
+    assert_eq!(dst.get_bits_as::<u32, _>(4..36), src[2]);
+    assert_eq!(dst.get_bits(0..4), 0x8);
+    assert_eq!(dst.get_bits(36..64), 0x09ab_cdef);
+
+    // a source which is a view itself
+    let mut dst = [0u32];
+    dst.copy_bits_from(2, &src.bit_slice(4..16), 4..);
+    assert_eq!(dst, [0x158]);
+}
+
+#[test]
+#[should_panic]
+fn test_copy_bits_from_overflowing_offset() {
+    let mut dst = [0u8; 2];
+    dst.copy_bits_from(usize::MAX - 2, &[0xffu8][..], 0..4);
+}
+
+#[test]
+fn test_move_bits() {
+    let original = [0x1234_5678u32, 0x9abc_def0, 0x0fed_cba9];
+    let len = original.bit_length();
+
+    for &(start, end, dst) in [
+        (0, 64, 5),
+        (5, 69, 0),
+        (10, 90, 13),
+        (31, 33, 64),
+        (7, 7, 3),
+    ]
+    .iter()
+    {
+        let mut value = original;
+        value.move_bits(start..end, dst);
+        for bit in 0..len {
+            let expected = if (dst..dst + end - start).contains(&bit) {
+                original.get_bit(bit - dst + start)
+            } else {
+                original.get_bit(bit)
+            };
+            assert_eq!(
+                value.get_bit(bit),
+                expected,
+                "{}..{} to {}: bit {}",
+                start,
+                end,
+                dst,
+                bit
+            );
+        }
+    }
+
+    let mut value = [0b0110u8];
+    value.bit_slice_mut(1..7).move_bits(0..4, 2);
+    assert_eq!(value, [0b1_1110]);
+}
+
+#[test]
+#[should_panic]
+fn test_move_bits_out_of_bounds() {
+    let mut value = [0u8; 2];
+    value.move_bits(0..8, 9);
+}
+
+#[test]
+#[should_panic]
+fn test_move_bits_overflowing_offset() {
+    let mut value = [0u8; 2];
+    value.move_bits(0..4, usize::MAX - 2);
+}
+
+#[test]
+fn test_shift_bits() {
+    let original = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+    let mut array = [0u32; 4];
+    array.set_bits_from(.., original);
+
+    for &n in [0, 1, 7, 32, 45, 127].iter() {
+        let mut value = array;
+        value.shl_bits(n);
+        assert_eq!(value.get_bits_as::<u128, _>(..), original << n, "shl {}", n);
+
+        let mut value = array;
+        value.shr_bits(n);
+        assert_eq!(value.get_bits_as::<u128, _>(..), original >> n, "shr {}", n);
+    }
+
+    let mut value = array;
+    value.shl_bits(128);
+    assert!(value.is_clear());
+    let mut value = array;
+    value.shr_bits(1000);
+    assert!(value.is_clear());
+
+    let mut value = [0xffffu16];
+    value.shl_bits_in(4..12, 3);
+    assert_eq!(value, [0xff8f]);
+    value.shr_bits_in(2..14, 20);
+    assert_eq!(value, [0xc003]);
+    value.shr_bits_in(8..8, 1);
+    assert_eq!(value, [0xc003]);
+
+    let mut value = [0b0111_1110u8];
+    value.bit_slice_mut(1..7).shl_bits(2);
+    assert_eq!(value, [0b0111_1000]);
+}
+
+#[test]
+fn test_rotate_bits() {
+    let original = 0x0123_4567_89ab_cdef_fedc_ba98_7654_3210u128;
+    let mut array = [0u8; 16];
+    array.set_bits_from(.., original);
+
+    for &n in [0, 1, 7, 8, 45, 127, 128, 300].iter() {
+        let mut value = array;
+        value.rotate_left_bits(n);
+        assert_eq!(
+            value.get_bits_as::<u128, _>(..),
+            original.rotate_left(n as u32),
+            "rotate left {}",
+            n
+        );
+
+        let mut value = array;
+        value.rotate_right_bits(n);
+        assert_eq!(
+            value.get_bits_as::<u128, _>(..),
+            original.rotate_right(n as u32),
+            "rotate right {}",
+            n
+        );
+    }
+
+    let mut value = [0b1000_0110u8];
+    value.bit_slice_mut(1..7).rotate_left_bits(4);
+    assert_eq!(value, [0b1110_0000]);
+    value.bit_slice_mut(1..7).rotate_right_bits(4);
+    assert_eq!(value, [0b1000_0110]);
+
+    let mut empty: [u8; 0] = [];
+    empty.rotate_left_bits(3);
+    empty.rotate_right_bits(3);
+}
+
+#[test]
+#[should_panic]
+fn test_shl_bits_in_out_of_bounds() {
+    let mut value = [0u8; 2];
+    value.shl_bits_in(4..17, 1);
+}
+
+#[test]
+fn test_rotate_bits_in_range() {
+    let original = 0x0123_4567_89ab_cdefu64;
+
+    for &n in [0, 1, 5, 31, 64, 100].iter() {
+        let mut value = original;
+        value.rotate_bits_left(.., n);
+        assert_eq!(value, original.rotate_left(n as u32));
+
+        let mut value = original;
+        value.rotate_bits_right(.., n);
+        assert_eq!(value, original.rotate_right(n as u32));
+
+        let mut value = original;
+        value.rotate_bits_left(8..24, n);
+        let len = 16;
+        for bit in 0..64 {
+            let expected = if (8..24).contains(&bit) {
+                original.get_bit(8 + (bit - 8 + len - n % len) % len)
+            } else {
+                original.get_bit(bit)
+            };
+            assert_eq!(value.get_bit(bit), expected, "rotate {}: bit {}", n, bit);
+        }
+        value.rotate_bits_right(8..24, n);
+        assert_eq!(value, original);
+    }
+
+    let mut value = -2i8;
+    value.rotate_bits_left(4..8, 1);
+    assert_eq!(value, -2);
+    value.rotate_bits_right(0..4, 1);
+    assert_eq!(value, -9);
+
+    let mut value = 1u128 << 127;
+    value.rotate_bits_left(100.., 3);
+    assert_eq!(value, 1 << 102);
+}
+
+#[test]
+fn test_reverse_and_swap_bytes_in_range() {
+    let mut value = 0x0123_4567_89ab_cdefu64;
+    value.reverse_bits_in(..);
+    assert_eq!(value, 0x0123_4567_89ab_cdefu64.reverse_bits());
+    value.reverse_bits_in(..);
+    value.swap_bytes_in(..);
+    assert_eq!(value, 0xefcd_ab89_6745_2301);
+
+    let mut value = 0b1010_0110u8;
+    value.reverse_bits_in(3..4);
+    assert_eq!(value, 0b1010_0110);
+    value.reverse_bits_in(1..7);
+    assert_eq!(value, 0b1110_0100);
+    value.swap_bytes_in(0..8);
+    assert_eq!(value, 0b1110_0100);
+
+    let mut value = 0x1234i16;
+    value.swap_bytes_in(..);
+    assert_eq!(value, 0x3412);
+    value.reverse_bits_in(12..16);
+    assert_eq!(value, 0xc412u16 as i16);
+}
+
+#[test]
+#[should_panic]
+fn test_swap_bytes_in_partial_byte() {
+    let mut value = 0u32;
+    value.swap_bytes_in(0..12);
+}
+
+#[test]
+#[should_panic]
+fn test_rotate_bits_empty_range() {
+    let mut value = 0u32;
+    value.rotate_bits_left(4..4, 1);
+}
+
+// Property tests for `extract_mask` and `deposit_mask`, which compare the methods against the
+// portable `const_fn` implementations and a bit-by-bit reference. With the `bmi2` feature on a
+// target with the `bmi2` target feature, the methods use the BMI2 instructions, so these tests
+// compare the BMI2 and the portable implementations.
+
+/// A deterministic xorshift generator for the property tests.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn next_u128(&mut self) -> u128 {
+        (self.next() as u128) << 64 | self.next() as u128
+    }
+}
+
+/// Gathers the bits of `value` selected by `mask` one bit at a time.
+fn extract_mask_reference(value: u128, mask: u128) -> u128 {
+    mask.iter_ones()
+        .enumerate()
+        .fold(0, |bits, (i, bit)| bits.with_bit(i, value.get_bit(bit)))
+}
+
+/// Scatters the bits of `bits` to the bits selected by `mask` one bit at a time.
+fn deposit_mask_reference(value: u128, mask: u128, bits: u128) -> u128 {
+    mask.iter_ones().enumerate().fold(value, |value, (i, bit)| {
+        value.with_bit(bit, bits.get_bit(i))
+    })
+}
+
+/// Returns `value` after depositing `bits` into it with `BitField::deposit_mask`.
+fn deposited<T: BitField + Copy>(mut value: T, mask: T, bits: T) -> T {
+    value.deposit_mask(mask, bits);
+    value
+}
+
+#[test]
+fn test_extract_mask() {
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..1000 {
+        let (value, mask) = (rng.next_u128(), rng.next_u128());
+        // sparse masks are the common case in practice
+        let sparse = mask & rng.next_u128();
+
+        for &mask in [mask, sparse, !0, 0].iter() {
+            let expected = extract_mask_reference(value, mask);
+            assert_eq!(value.extract_mask(mask), expected);
+            assert_eq!(const_fn::u128::extract_mask(value, mask), expected);
+
+            let (value, mask) = (value as u64, mask as u64);
+            let expected = extract_mask_reference(value as u128, mask as u128) as u64;
+            assert_eq!(value.extract_mask(mask), expected);
+            assert_eq!(const_fn::u64::extract_mask(value, mask), expected);
+
+            let (value, mask) = (value as i32, mask as i32);
+            assert_eq!(
+                value.extract_mask(mask),
+                const_fn::i32::extract_mask(value, mask)
+            );
+            let (value, mask) = (value as u8, mask as u8);
+            assert_eq!(
+                value.extract_mask(mask),
+                const_fn::u8::extract_mask(value, mask)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_deposit_mask() {
+    let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+
+    for _ in 0..1000 {
+        let (value, mask, bits) = (rng.next_u128(), rng.next_u128(), rng.next_u128());
+        let sparse = mask & rng.next_u128();
+
+        for &mask in [mask, sparse, !0, 0].iter() {
+            let expected = deposit_mask_reference(value, mask, bits);
+            assert_eq!(deposited(value, mask, bits), expected);
+            assert_eq!(const_fn::u128::deposit_mask(value, mask, bits), expected);
+
+            let (value, mask, bits) = (value as u64, mask as u64, bits as u64);
+            let expected = deposit_mask_reference(value as u128, mask as u128, bits as u128) as u64;
+            assert_eq!(deposited(value, mask, bits), expected);
+            assert_eq!(const_fn::u64::deposit_mask(value, mask, bits), expected);
+
+            let (value, mask, bits) = (value as i16, mask as i16, bits as i16);
+            assert_eq!(
+                deposited(value, mask, bits),
+                const_fn::i16::deposit_mask(value, mask, bits)
+            );
+            let (value, mask, bits) = (value as usize, mask as usize, bits as usize);
+            assert_eq!(
+                deposited(value, mask, bits),
+                const_fn::usize::deposit_mask(value, mask, bits)
+            );
+
+            // extracting the deposited bits gives back the bits which fit into the mask
+            let value = deposited(value, mask, bits);
+            let len = mask.count_ones() as usize;
+            assert_eq!(
+                value.extract_mask(mask),
+                if len == 0 { 0 } else { bits.get_bits(..len) }
+            );
+        }
+    }
+}
+
+#[test]
+fn test_split_bits() {
+    let base = [16..40, 56..64];
+    let limit = [0..16, 48..52];
+
+    let mut descriptor = 0u64;
+    descriptor.set_split_bits(&base, 0xdead_beef);
+    descriptor.set_split_bits(&limit, 0xa_bcde);
+    assert_eq!(descriptor, 0xde0a_00ad_beef_bcde);
+    assert_eq!(descriptor.get_split_bits(&base), 0xdead_beef);
+    assert_eq!(descriptor.get_split_bits(&limit), 0xa_bcde);
+
+    // the pieces don't need to be in order
+    assert_eq!(0b1100_0011u8.get_split_bits(&[6..8, 0..2]), 0b1111);
+    assert_eq!(0b1000_0001u8.get_split_bits(&[7..8, 0..1, 4..5]), 0b011);
+
+    let mut value = 0i16;
+    value.set_split_bits(&[12..16, 0..4], -2);
+    assert_eq!(value, 0xe00f_u16 as i16);
+    assert_eq!(value.get_split_bits(&[12..16, 0..4]), 0xfe);
+    value.set_split_bits(&[0..8, 8..16], -1);
+    assert_eq!(value, -1);
+    assert_eq!(value.get_split_bits(&[0..8, 8..16]), -1);
+
+    let mut array = [0u8; 8];
+    array.set_split_bits(&base, 0xdead_beefu32);
+    array.set_split_bits(&limit, 0xa_bcdeu32);
+    assert_eq!(array, [0xde, 0xbc, 0xef, 0xbe, 0xad, 0x00, 0x0a, 0xde]);
+    assert_eq!(array.get_split_bits::<u32>(&base), 0xdead_beef);
+    assert_eq!(array.get_split_bits::<u64>(&[0..32, 32..64]), descriptor);
+
+    let mut array = [0u8; 2];
+    array
+        .bit_slice_mut(4..16)
+        .set_split_bits(&[8..12, 0..4], 0x5au8);
+    assert_eq!(array, [0x50, 0xa0]);
+    assert_eq!(
+        array.bit_slice(4..16).get_split_bits::<u8>(&[8..12, 0..4]),
+        0x5a
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_split_bits_overlap() {
+    0u32.get_split_bits(&[0..8, 4..12]);
+}
+
+#[test]
+#[should_panic]
+fn test_split_bits_too_long() {
+    [0u32; 2].get_split_bits::<u8>(&[0..4, 32..37]);
+}
+
+#[test]
+#[should_panic]
+fn test_set_split_bits_too_large() {
+    let mut array = [0u8; 2];
+    array.set_split_bits(&[0..4, 8..12], 0x100u16);
+}
+
+#[test]
+#[should_panic(expected = "no bit ranges given")]
+fn test_split_bits_no_ranges() {
+    0u32.get_split_bits(&[]);
+}
+
+#[test]
+#[should_panic(expected = "no bit ranges given")]
+fn test_set_split_bits_no_ranges() {
+    let mut array = [0u8; 2];
+    array.set_split_bits(&[], 0u8);
+}
+
+#[test]
+#[should_panic(expected = "bit range is empty or reversed")]
+fn test_split_bits_empty_range() {
+    [0u8; 2].get_split_bits::<u8>(&[0..4, 6..6]);
+}
+
+#[test]
+#[should_panic(expected = "bit range is out of bounds")]
+fn test_split_bits_out_of_bounds() {
+    let mut value = 0u16;
+    value.set_split_bits(&[0..4, 12..17], 0);
+}
+
+#[test]
+fn test_field() {
+    const LOW: Field<u16, 0, 4> = Field::new();
+    const HIGH: Field<u16, 8, 16> = Field::new();
+    const SIGN: Field<i32, 31, 32> = Field::new();
+
+    let mut value = 0u16;
+    LOW.set(&mut value, 0xa);
+    HIGH.set(&mut value, 0xbc);
+    assert_eq!(value, 0xbc0a);
+    assert_eq!(LOW.get(&value), 0xa);
+    assert_eq!(HIGH.get(&value), 0xbc);
+    assert_eq!(LOW.with(value, 0x3), 0xbc03);
+
+    assert_eq!(HIGH.range(), 8..16);
+    assert_eq!(HIGH.len(), 8);
+
+    let mut value = 0x7fff_ffffi32;
+    SIGN.set(&mut value, 1);
+    assert_eq!(value, -1);
+    assert_eq!(SIGN.get(&value), 1);
+
+    // negative values which fit into the field in two's complement
+    let mut value = 0i8;
+    Field::<i8, 2, 6>::new().set(&mut value, -2);
+    assert_eq!(value, 0b11_1000);
+
+    let mut value = !0u128;
+    Field::<u128, 0, 128>::new().set(&mut value, 0x1234);
+    assert_eq!(value, 0x1234);
+    assert_eq!(Field::<u128, 120, 128>::new().get(&!0), 0xff);
+
+    let mut flags = Flags(0xdead);
+    Field::<Flags, 4, 12>::new().set(&mut flags, Flags(0x12));
+    assert_eq!(flags, Flags(0xd12d));
+    assert_eq!(Field::<Flags, 8, 16>::new().get(&flags), Flags(0xd1));
+}
+
+#[test]
+#[should_panic]
+fn test_field_value_too_large() {
+    let mut value = 0u8;
+    Field::<u8, 2, 4>::new().set(&mut value, 0b100);
+}
+
+const INDEX: usize = 3;
+
+bitfield! {
+    /// A segment selector, with a field of each kind.
+    struct Selector(u16) {
+        privilege_level, set_privilege_level, with_privilege_level: 0..2 => u8,
+        local, set_local, with_local: 2..3 => bool,
+        index, set_index, with_index: INDEX..INDEX + 13,
+    }
+}
+
+bitfield! {
+    /// A status register with read-only fields.
+    struct Status(u8) {
+        ready: 0..1 => bool,
+        error_code: 4..8,
+    }
+}
+
+#[test]
+fn test_bitfield_macro() {
+    const KERNEL_DATA: Selector = Selector::new().with_index(2);
+
+    assert_eq!(KERNEL_DATA.bits(), 0x10);
+    assert_eq!(KERNEL_DATA.index(), 2);
+    assert_eq!(KERNEL_DATA.privilege_level(), 0);
+    assert!(!KERNEL_DATA.local());
+
+    let mut selector = KERNEL_DATA.with_privilege_level(3);
+    selector.set_local(true);
+    selector.set_index(0x1fff);
+    assert_eq!(selector.bits(), 0xffff);
+    assert_eq!(Selector::from_bits(0xffff).privilege_level(), 3);
+
+    selector.set_local(false);
+    assert_eq!(selector.bits(), 0xfffb);
+    assert_eq!(Selector::default().bits(), 0);
+
+    const STATUS: Status = Status::from_bits(0xa1);
+    assert!(STATUS.ready());
+    assert_eq!(STATUS.error_code(), 0xa);
+    assert!(!Status::new().ready());
+    assert_eq!(Status::default().bits(), 0);
+}
+
+#[test]
+#[should_panic]
+fn test_bitfield_macro_value_too_large() {
+    Selector::new().set_privilege_level(4);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MemoryType {
+    Uncacheable = 0,
+    WriteCombining = 1,
+    WriteBack = 6,
+}
+
+impl TryFrom<u64> for MemoryType {
+    type Error = u64;
+
+    fn try_from(value: u64) -> Result<Self, u64> {
+        match value {
+            0 => Ok(MemoryType::Uncacheable),
+            1 => Ok(MemoryType::WriteCombining),
+            6 => Ok(MemoryType::WriteBack),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<MemoryType> for u64 {
+    fn from(memory_type: MemoryType) -> u64 {
+        memory_type as u64
+    }
+}
+
+#[test]
+fn test_get_set_field() {
+    let mut value = 0xffff_0000_0000_00ffu64;
+
+    value.set_field(8..11, MemoryType::WriteBack);
+    assert_eq!(value, 0xffff_0000_0000_06ff);
+    assert_eq!(value.get_field(8..11), Ok(MemoryType::WriteBack));
+
+    value.set_field(8..11, MemoryType::WriteCombining);
+    assert_eq!(value.get_field(8..=10), Ok(MemoryType::WriteCombining));
+    assert_eq!(
+        value.get_field::<MemoryType, _>(4..7),
+        Err(InvalidFieldValue {
+            value: 0b111,
+            range: 4..7
+        })
+    );
+
+    // any `TryFrom` conversion works, not just enums
+    assert_eq!(0x1234u16.get_field::<u8, _>(4..12), Ok(0x23));
+    assert_eq!(
+        0xf0i32.get_field::<i8, _>(0..8).map_err(|e| e.value),
+        Err(0xf0)
+    );
+    let mut value = 0u32;
+    value.set_field(28.., 0xau8);
+    assert_eq!(value, 0xa000_0000);
+}
+
+#[test]
+#[should_panic]
+fn test_set_field_too_large() {
+    let mut value = 0u64;
+    value.set_field(0..2, MemoryType::WriteBack);
+}
+
+#[test]
+fn test_msb0() {
+    let mut value = 0u32;
+    value.set_bit_msb0(0, true).set_bits_msb0(8..20, 0xabc);
+    assert_eq!(value, 0x80ab_c000);
+    assert!(value.get_bit_msb0(0));
+    assert!(!value.get_bit_msb0(31));
+    assert_eq!(value.get_bits_msb0(8..20), 0xabc);
+    assert_eq!(value.get_bits_msb0(..), value);
+
+    let mut value = 0i8;
+    value.set_bits_msb0(0..4, -2);
+    assert_eq!(value, -32);
+    assert_eq!(value.get_bits_msb0(0..4), 0b1110);
+
+    // a TCP header: source port, destination port, sequence number, data offset and flags
+    let mut header = [0u8; 16];
+    header.set_bits_msb0(0..16, 443u16);
+    header.set_bits_msb0(16..32, 0xc350u16);
+    header.set_bits_msb0(32..64, 0xdead_beefu32);
+    header.set_bits_msb0(96..100, 5u8);
+    header.set_bit_msb0(107, true);
+    assert_eq!(
+        header,
+        [0x01, 0xbb, 0xc3, 0x50, 0xde, 0xad, 0xbe, 0xef, 0, 0, 0, 0, 0x50, 0x10, 0, 0]
+    );
+    assert_eq!(header.get_bits_msb0::<u16, _>(0..16), 443);
+    assert_eq!(header.get_bits_msb0::<u32, _>(32..64), 0xdead_beef);
+    assert_eq!(header.get_bits_msb0::<u8, _>(96..100), 5);
+    assert!(header.get_bit_msb0(107));
+    assert_eq!(
+        header.get_bits_msb0::<u128, _>(..),
+        u128::from_be_bytes(header)
+    );
+
+    // the bits of wider elements are numbered from the most significant bit of each element
+    let mut value = [0u16; 2];
+    value.set_bits_msb0(12..20, 0xabu8);
+    assert_eq!(value, [0x000a, 0xb000]);
+    assert_eq!(value.get_bits_msb0::<u8, _>(12..20), 0xab);
+    assert_eq!(value.bit_slice(0..32).get_bits_msb0::<u8, _>(12..20), 0xab);
+
+    let mut bits = BitArr::<16, 2, u8>::new();
+    bits.set_bits_msb0(4..12, 0x5au8);
+    assert_eq!(bits.as_slice(), &[0x05, 0xa0]);
+    assert_eq!(bits.get_bits_msb0::<u8, _>(4..12), 0x5a);
+}
+
+#[test]
+fn test_msb0_partial_element() {
+    // the last element of a 12-bit array is counted from its fourth bit
+    let mut elements = [0u8; 2];
+    {
+        let mut view = elements.bit_slice_mut(0..12);
+        view.set_bits_msb0(4..12, 0xa5u8);
+        assert_eq!(view.get_bits_msb0::<u8, _>(4..12), 0xa5);
+        assert!(view.get_bit_msb0(11));
+        assert!(!view.get_bit_msb0(10));
+        view.set_bit_msb0(8, true);
+    }
+    assert_eq!(elements, [0x0a, 0x0d]);
+    assert_eq!(elements.bit_slice(..12).get_bits_msb0::<u16, _>(..), 0x0ad);
+
+    let mut bits = BitArr::<17, 3, u8>::new();
+    bits.set_bit_msb0(0, true);
+    bits.set_bit_msb0(16, true);
+    assert_eq!(bits.as_slice(), &[0x80, 0, 0x01]);
+    assert_eq!(bits.get_bits_msb0::<u32, _>(..), 1 << 16 | 1);
+    assert!(bits.get_bit_msb0(16));
+
+    let mut bits = BitArr::<100, 2, u64>::new();
+    bits.set_bits_msb0(64..=91, 0xabc_def1u32);
+    assert_eq!(bits.as_slice(), &[0, 0xa_bcde_f100]);
+    assert_eq!(bits.get_bits_msb0::<u32, _>(64..=91), 0xabc_def1);
+    bits.set_bit_msb0(99, true);
+    assert!(bits.get_bit(64));
+    assert_eq!(bits.get_bits_msb0::<u64, _>(60..100), 0xa_bcde_f101);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_msb0_partial_element_bit_vec() {
+    let mut bits: BitVec<u64> = BitVec::repeat(false, 100);
+    bits.set_bits_msb0(64..=91, 0xabc_def1u32);
+    assert_eq!(bits.as_slice(), &[0, 0xa_bcde_f100]);
+    assert_eq!(bits.get_bits_msb0::<u32, _>(64..=91), 0xabc_def1);
+    assert!(bits.get_bit_msb0(64));
+    assert!(!bits.get_bit_msb0(99));
+}
+
+#[test]
+#[should_panic]
+fn test_msb0_12_bits_out_of_bounds() {
+    let mut elements = [0u8; 2];
+    elements.bit_slice_mut(0..12).set_bit_msb0(12, true);
+}
+
+#[test]
+#[should_panic]
+fn test_msb0_12_bits_range_out_of_bounds() {
+    let elements = [0u8; 2];
+    elements.bit_slice(0..12).get_bits_msb0::<u8, _>(6..13);
+}
+
+#[test]
+#[should_panic]
+fn test_msb0_17_bits_out_of_bounds() {
+    BitArr::<17, 3, u8>::new().get_bit_msb0(17);
+}
+
+#[test]
+#[should_panic]
+fn test_msb0_100_bits_out_of_bounds() {
+    BitArr::<100, 2, u64>::new().get_bit_msb0(120);
+}
+
+#[test]
+#[should_panic]
+fn test_msb0_100_bits_range_out_of_bounds() {
+    BitArr::<100, 2, u64>::new().set_bits_msb0(90..101, 0u16);
+}
+
+#[test]
+#[should_panic]
+fn test_msb0_reversed_range() {
+    let start = 40;
+    0u32.get_bits_msb0(start..30);
+}